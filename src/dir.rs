@@ -0,0 +1,198 @@
+//! Directory streams
+//!
+//! [Further reading](http://man7.org/linux/man-pages/man3/readdir.3.html)
+use {Error, Result, NixPath};
+use errno::Errno;
+use fcntl::{self, OFlag};
+use fd::Fd;
+use libc::{self, c_long};
+use sys::stat::{FileType, Mode};
+use std::ffi::{CStr, OsStr};
+use std::fmt;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd};
+
+mod ffi {
+    use libc::{c_int, c_long, DIR, dirent};
+
+    extern {
+        pub fn fdopendir(fd: c_int) -> *mut DIR;
+        pub fn readdir(dirp: *mut DIR) -> *mut dirent;
+        pub fn rewinddir(dirp: *mut DIR);
+        pub fn seekdir(dirp: *mut DIR, loc: c_long);
+        pub fn telldir(dirp: *mut DIR) -> c_long;
+        pub fn dirfd(dirp: *mut DIR) -> c_int;
+        pub fn closedir(dirp: *mut DIR) -> c_int;
+    }
+}
+
+/// An open directory stream, closed when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use nix::dir::Dir;
+/// use nix::fcntl::{O_RDONLY, O_DIRECTORY};
+/// use nix::sys::stat::Mode;
+///
+/// let mut dir = Dir::open("/".as_bytes(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+/// for entry in dir.iter() {
+///     let entry = entry.unwrap();
+///     println!("{:?} {:?}", entry.file_name(), entry.file_type());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Dir(*mut libc::DIR);
+
+impl Dir {
+    /// Opens the directory at `path`.
+    pub fn open<P: ?Sized + NixPath>(path: &P, oflag: OFlag, mode: Mode) -> Result<Dir> {
+        let fd = try!(fcntl::open_owned(path, oflag, mode));
+        Dir::from_fd(fd)
+    }
+
+    /// Opens the directory at `path` relative to `dirfd`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn openat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, oflag: OFlag, mode: Mode) -> Result<Dir> {
+        let fd = try!(fcntl::openat_owned(dirfd, path, oflag, mode));
+        Dir::from_fd(fd)
+    }
+
+    /// Takes ownership of an open directory descriptor, via `fdopendir`.
+    ///
+    /// The descriptor is closed when the `Dir` is dropped, or right away if
+    /// `fdopendir` fails.
+    pub fn from_fd(fd: Fd) -> Result<Dir> {
+        let dirp = unsafe { ffi::fdopendir(fd.as_raw_fd()) };
+
+        if dirp.is_null() {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        // The stream now owns the descriptor
+        fd.into_raw_fd();
+        Ok(Dir(dirp))
+    }
+
+    /// Returns an iterator over the remaining entries of the directory stream.
+    pub fn iter(&mut self) -> Iter {
+        Iter(self)
+    }
+
+    /// Resets the stream to the beginning of the directory.
+    pub fn rewind(&mut self) {
+        unsafe { ffi::rewinddir(self.0) }
+    }
+
+    /// Returns the current location of the stream, for use with `seek`.
+    pub fn tell(&mut self) -> Result<c_long> {
+        let res = unsafe { ffi::telldir(self.0) };
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(res)
+    }
+
+    /// Moves the stream to a location previously returned by `tell`.
+    pub fn seek(&mut self, loc: c_long) {
+        unsafe { ffi::seekdir(self.0, loc) }
+    }
+}
+
+impl AsRawFd for Dir {
+    fn as_raw_fd(&self) -> RawFd {
+        unsafe { ffi::dirfd(self.0) }
+    }
+}
+
+impl Drop for Dir {
+    fn drop(&mut self) {
+        unsafe { ffi::closedir(self.0) };
+    }
+}
+
+/// Iterator over the entries of a `Dir`, including `.` and `..`.
+pub struct Iter<'d>(&'d mut Dir);
+
+impl<'d> Iterator for Iter<'d> {
+    type Item = Result<Entry>;
+
+    fn next(&mut self) -> Option<Result<Entry>> {
+        unsafe {
+            // readdir only sets errno on error, so it has to be cleared first
+            // to tell the end of the stream apart from a failure.
+            Errno::clear();
+            let ent = ffi::readdir((self.0).0);
+
+            if ent.is_null() {
+                match Errno::last() {
+                    Errno::UnknownErrno => None,
+                    errno => Some(Err(Error::Sys(errno))),
+                }
+            } else {
+                Some(Ok(Entry(*ent)))
+            }
+        }
+    }
+}
+
+impl<'d> IntoIterator for &'d mut Dir {
+    type Item = Result<Entry>;
+    type IntoIter = Iter<'d>;
+
+    fn into_iter(self) -> Iter<'d> {
+        self.iter()
+    }
+}
+
+/// A directory entry, copied out of the stream.
+#[derive(Clone, Copy)]
+pub struct Entry(libc::dirent);
+
+impl Entry {
+    /// Returns the inode number of the entry.
+    #[cfg(not(any(target_os = "freebsd", target_os = "dragonfly", target_os = "openbsd", target_os = "netbsd")))]
+    pub fn ino(&self) -> u64 {
+        self.0.d_ino as u64
+    }
+
+    /// Returns the inode number of the entry.
+    #[cfg(any(target_os = "freebsd", target_os = "dragonfly", target_os = "openbsd", target_os = "netbsd"))]
+    pub fn ino(&self) -> u64 {
+        self.0.d_fileno as u64
+    }
+
+    /// Returns the bare file name of the entry, without any leading path.
+    pub fn file_name(&self) -> &CStr {
+        unsafe { CStr::from_ptr(self.0.d_name.as_ptr()) }
+    }
+
+    /// Like `file_name`, as an `OsStr`.
+    pub fn file_name_os(&self) -> &OsStr {
+        OsStr::from_bytes(self.file_name().to_bytes())
+    }
+
+    /// Returns the type of the entry, or `None` if the filesystem does not
+    /// report it (`DT_UNKNOWN`), in which case `fstatat` has to be used.
+    pub fn file_type(&self) -> Option<FileType> {
+        match self.0.d_type {
+            libc::DT_FIFO => Some(FileType::Fifo),
+            libc::DT_CHR => Some(FileType::CharacterDevice),
+            libc::DT_DIR => Some(FileType::Directory),
+            libc::DT_BLK => Some(FileType::BlockDevice),
+            libc::DT_REG => Some(FileType::File),
+            libc::DT_LNK => Some(FileType::Symlink),
+            libc::DT_SOCK => Some(FileType::Socket),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Entry {{ ino: {}, file_name: {:?}, file_type: {:?} }}",
+               self.ino(), self.file_name(), self.file_type())
+    }
+}
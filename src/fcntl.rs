@@ -1,8 +1,24 @@
-use {Error, Result, NixPath};
+use {Error, Result, NixPath, from_ffi};
 use errno::Errno;
-use libc::{c_int, c_uint};
+use fd::{Fd, BorrowedFd};
+use libc::{c_int, c_uint, off_t};
 use sys::stat::Mode;
-use std::os::unix::io::RawFd;
+use unistd::Whence;
+use std::mem;
+use std::os::unix::io::{RawFd, AsRawFd, FromRawFd};
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use libc::{c_short, pid_t, size_t};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use sys::signal::Signal;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::convert::TryFrom;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::ffi::OsString;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::ptr;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use sys::uio::IoVec;
 
 pub use self::consts::*;
 pub use self::ffi::flock;
@@ -16,7 +32,24 @@ mod ffi {
 
     #[cfg(any(target_os = "linux", target_os = "android"))]
     mod os {
-        use libc::{c_int, c_short, off_t, pid_t};
+        use libc::{c_char, c_int, c_short, c_uint, off_t, pid_t, size_t, ssize_t};
+        use sys::uio::IoVec;
+        use super::super::loff_t;
+
+        extern {
+            pub fn openat(dirfd: c_int, pathname: *const c_char, flags: c_int, ...) -> c_int;
+            pub fn renameat(olddirfd: c_int, oldpath: *const c_char,
+                            newdirfd: c_int, newpath: *const c_char) -> c_int;
+            pub fn readlinkat(dirfd: c_int, pathname: *const c_char,
+                              buf: *mut c_char, bufsiz: size_t) -> ssize_t;
+
+            pub fn splice(fd_in: c_int, off_in: *mut loff_t, fd_out: c_int, off_out: *mut loff_t,
+                          len: size_t, flags: c_uint) -> ssize_t;
+            pub fn tee(fd_in: c_int, fd_out: c_int, len: size_t, flags: c_uint) -> ssize_t;
+            pub fn vmsplice(fd: c_int, iov: *const IoVec<&[u8]>, nr_segs: size_t, flags: c_uint) -> ssize_t;
+            pub fn copy_file_range(fd_in: c_int, off_in: *mut loff_t, fd_out: c_int, off_out: *mut loff_t,
+                                   len: size_t, flags: c_uint) -> ssize_t;
+        }
 
         #[repr(C)]
         #[derive(Clone, Copy, Default, Debug)]
@@ -31,6 +64,17 @@ mod ffi {
             pub l_sysid: c_int,
         }
 
+        #[repr(C)]
+        #[derive(Clone, Copy, Default, Debug)]
+        pub struct f_owner_ex {
+            pub type_: c_int,
+            pub pid: pid_t,
+        }
+
+        pub const F_OWNER_TID:     c_int = 0;
+        pub const F_OWNER_PID:     c_int = 1;
+        pub const F_OWNER_PGRP:    c_int = 2;
+
         pub const F_DUPFD:         c_int = 0;
         pub const F_DUPFD_CLOEXEC: c_int = 1030;
         pub const F_GETFD:         c_int = 1;
@@ -40,9 +84,29 @@ mod ffi {
         pub const F_SETLK:         c_int = 6;
         pub const F_SETLKW:        c_int = 7;
         pub const F_GETLK:         c_int = 5;
+        pub const F_SETOWN:        c_int = 8;
+        pub const F_GETOWN:        c_int = 9;
+        pub const F_SETSIG:        c_int = 10;
+        pub const F_GETSIG:        c_int = 11;
+        pub const F_SETOWN_EX:     c_int = 15;
+        pub const F_GETOWN_EX:     c_int = 16;
+        pub const F_OFD_GETLK:     c_int = 36;
+        pub const F_OFD_SETLK:     c_int = 37;
+        pub const F_OFD_SETLKW:    c_int = 38;
 
+        pub const F_SETLEASE:      c_int = 1024;
+        pub const F_GETLEASE:      c_int = 1025;
+        pub const F_NOTIFY:        c_int = 1026;
+        pub const F_SETPIPE_SZ:    c_int = 1031;
+        pub const F_GETPIPE_SZ:    c_int = 1032;
         pub const F_ADD_SEALS:     c_int = 1033;
         pub const F_GET_SEALS:     c_int = 1034;
+        pub const F_GET_RW_HINT:   c_int = 1035;
+        pub const F_SET_RW_HINT:   c_int = 1036;
+
+        pub const F_RDLCK:         c_short = 0;
+        pub const F_WRLCK:         c_short = 1;
+        pub const F_UNLCK:         c_short = 2;
 
         pub const F_SEAL_SEAL:     c_int = 1;
         pub const F_SEAL_SHRINK:   c_int = 2;
@@ -109,6 +173,150 @@ pub fn open<P: ?Sized + NixPath>(path: &P, oflag: OFlag, mode: Mode) -> Result<R
     Ok(fd)
 }
 
+/// Like `open`, but returns an owned `Fd` that is closed on drop.
+pub fn open_owned<P: ?Sized + NixPath>(path: &P, oflag: OFlag, mode: Mode) -> Result<Fd> {
+    open(path, oflag, mode).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
+/// Resolves the directory argument of the `*at` functions, where `None` means
+/// relative to the current working directory (`AT_FDCWD`).
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[inline]
+pub(crate) fn at_rawfd(dirfd: Option<RawFd>) -> RawFd {
+    dirfd.unwrap_or(AT_FDCWD)
+}
+
+/// Opens `path` relative to the directory `dirfd`.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/openat.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn openat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, oflag: OFlag, mode: Mode) -> Result<RawFd> {
+    let fd = try!(path.with_nix_path(|cstr| {
+        unsafe { ffi::openat(at_rawfd(dirfd), cstr.as_ptr(), oflag.bits(), mode.bits() as c_uint) }
+    }));
+
+    if fd < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(fd)
+}
+
+/// Like `openat`, but returns an owned `Fd` that is closed on drop.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn openat_owned<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, oflag: OFlag, mode: Mode) -> Result<Fd> {
+    openat(dirfd, path, oflag, mode).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
+/// How `openat2` should open a path: the usual `open` flags and mode, plus
+/// restrictions on how the path may be resolved.
+#[cfg(target_os = "linux")]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpenHow {
+    flags: u64,
+    mode: u64,
+    resolve: u64,
+}
+
+#[cfg(target_os = "linux")]
+impl OpenHow {
+    pub fn new(oflag: OFlag, mode: Mode, resolve: ResolveFlag) -> OpenHow {
+        OpenHow {
+            flags: oflag.bits() as u64,
+            mode: mode.bits() as u64,
+            resolve: resolve.bits(),
+        }
+    }
+
+    pub fn oflag(&self) -> OFlag {
+        OFlag::from_bits_truncate(self.flags as c_int)
+    }
+
+    pub fn mode(&self) -> Mode {
+        Mode::from_bits_truncate(self.mode as ::libc::mode_t)
+    }
+
+    pub fn resolve(&self) -> ResolveFlag {
+        ResolveFlag::from_bits_truncate(self.resolve)
+    }
+}
+
+/// Like `openat`, but with control over path resolution, e.g. `RESOLVE_IN_ROOT`
+/// to treat `dirfd` as the root directory while looking up `path`.
+///
+/// Fails with `ENOSYS` on kernels older than 5.6.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/openat2.2.html)
+#[cfg(target_os = "linux")]
+pub fn openat2<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, how: &OpenHow) -> Result<RawFd> {
+    use std::mem;
+    use sys::syscall::{syscall, OPENAT2};
+
+    let fd = try!(path.with_nix_path(|cstr| unsafe {
+        syscall(OPENAT2, at_rawfd(dirfd), cstr.as_ptr(), how as *const OpenHow,
+                mem::size_of::<OpenHow>() as size_t)
+    }));
+
+    if fd < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(fd)
+}
+
+/// Like `openat2`, but returns an owned `Fd` that is closed on drop.
+#[cfg(target_os = "linux")]
+pub fn openat2_owned<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, how: &OpenHow) -> Result<Fd> {
+    openat2(dirfd, path, how).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
+/// Renames `old_path` relative to `old_dirfd` to `new_path` relative to `new_dirfd`.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/renameat.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn renameat<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(old_dirfd: Option<RawFd>, old_path: &P1,
+                                                           new_dirfd: Option<RawFd>, new_path: &P2)
+                                                           -> Result<()> {
+    let res = try!(try!(old_path.with_nix_path(|old_cstr| {
+        new_path.with_nix_path(|new_cstr| unsafe {
+            ffi::renameat(at_rawfd(old_dirfd), old_cstr.as_ptr(),
+                          at_rawfd(new_dirfd), new_cstr.as_ptr())
+        })
+    })));
+    from_ffi(res)
+}
+
+/// Like `renameat`, with `RENAME_NOREPLACE` to fail instead of replacing an
+/// existing `new_path`, or `RENAME_EXCHANGE` to atomically swap both paths.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/renameat2.2.html)
+#[cfg(target_os = "linux")]
+pub fn renameat2<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(old_dirfd: Option<RawFd>, old_path: &P1,
+                                                            new_dirfd: Option<RawFd>, new_path: &P2,
+                                                            flags: RenameFlags)
+                                                            -> Result<()> {
+    use sys::syscall::{syscall, RENAMEAT2};
+
+    let res = try!(try!(old_path.with_nix_path(|old_cstr| {
+        new_path.with_nix_path(|new_cstr| unsafe {
+            syscall(RENAMEAT2, at_rawfd(old_dirfd), old_cstr.as_ptr(),
+                    at_rawfd(new_dirfd), new_cstr.as_ptr(), flags.bits())
+        })
+    })));
+    from_ffi(res)
+}
+
+/// Reads the target of the symbolic link `path` relative to `dirfd`.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/readlinkat.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn readlinkat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P) -> Result<OsString> {
+    ::unistd::read_link_target(|buf, len| path.with_nix_path(|cstr| unsafe {
+        ffi::readlinkat(at_rawfd(dirfd), cstr.as_ptr(), buf, len)
+    }))
+}
+
 pub enum FcntlArg<'a> {
     F_DUPFD(RawFd),
     F_DUPFD_CLOEXEC(RawFd),
@@ -125,43 +333,368 @@ pub enum FcntlArg<'a> {
     F_OFD_SETLKW(&'a flock),
     #[cfg(any(target_os = "linux", target_os = "android"))]
     F_OFD_GETLK(&'a mut flock),
+    /// Sets the process (positive) or process group (negative) receiving `SIGIO`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_SETOWN(pid_t),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_GETOWN,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_SETOWN_EX(FOwner),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_GETOWN_EX,
+    /// Sets the signal sent instead of `SIGIO`, or `None` to restore `SIGIO`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_SETSIG(Option<Signal>),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_GETSIG,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_SETLEASE(LeaseType),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_GETLEASE,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_NOTIFY(DnotifyFlag),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_SETPIPE_SZ(c_int),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    F_GETPIPE_SZ,
     #[cfg(target_os = "linux")]
     F_ADD_SEALS(SealFlag),
     #[cfg(target_os = "linux")]
     F_GET_SEALS,
+    #[cfg(target_os = "linux")]
+    F_SET_RW_HINT(RwHint),
+    #[cfg(target_os = "linux")]
+    F_GET_RW_HINT,
+}
+
+/// The result of a successful `fcntl` call, typed according to the command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FcntlRet {
+    /// The command has no meaningful result (e.g. `F_SETFD`, `F_SETLK`).
+    Done,
+    /// The new descriptor from `F_DUPFD` and `F_DUPFD_CLOEXEC`.
+    Fd(RawFd),
+    FdFlag(FdFlag),
+    OFlag(OFlag),
+    /// The process (positive) or process group (negative) from `F_GETOWN`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Owner(pid_t),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    OwnerEx(FOwner),
+    /// The signal from `F_GETSIG`, where `None` means `SIGIO`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Signal(Option<Signal>),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Lease(LeaseType),
+    /// The pipe capacity in bytes, from `F_GETPIPE_SZ` and `F_SETPIPE_SZ`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    PipeSize(c_int),
+    #[cfg(target_os = "linux")]
+    SealFlag(SealFlag),
+    #[cfg(target_os = "linux")]
+    RwHint(RwHint),
+}
 
-    // TODO: Rest of flags
+/// The target of `SIGIO`/`SIGURG` signals, for `F_SETOWN_EX` and `F_GETOWN_EX`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FOwner {
+    Thread(pid_t),
+    Process(pid_t),
+    ProcessGroup(pid_t),
 }
 
-// TODO: Figure out how to handle value fcntl returns
-pub fn fcntl(fd: RawFd, arg: FcntlArg) -> Result<c_int> {
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl FOwner {
+    fn to_ffi(self) -> ffi::f_owner_ex {
+        let (type_, pid) = match self {
+            FOwner::Thread(pid) => (ffi::F_OWNER_TID, pid),
+            FOwner::Process(pid) => (ffi::F_OWNER_PID, pid),
+            FOwner::ProcessGroup(pid) => (ffi::F_OWNER_PGRP, pid),
+        };
+        ffi::f_owner_ex { type_: type_, pid: pid }
+    }
+
+    fn from_ffi(owner: ffi::f_owner_ex) -> Result<FOwner> {
+        match owner.type_ {
+            ffi::F_OWNER_TID => Ok(FOwner::Thread(owner.pid)),
+            ffi::F_OWNER_PID => Ok(FOwner::Process(owner.pid)),
+            ffi::F_OWNER_PGRP => Ok(FOwner::ProcessGroup(owner.pid)),
+            _ => Err(Error::invalid_argument()),
+        }
+    }
+}
+
+/// The kind of lease taken with `F_SETLEASE`; `Unlock` removes it.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeaseType {
+    Read,
+    Write,
+    Unlock,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl LeaseType {
+    fn from_c_int(lease: c_int) -> Result<LeaseType> {
+        match lease as c_short {
+            ffi::F_RDLCK => Ok(LeaseType::Read),
+            ffi::F_WRLCK => Ok(LeaseType::Write),
+            ffi::F_UNLCK => Ok(LeaseType::Unlock),
+            _ => Err(Error::invalid_argument()),
+        }
+    }
+
+    fn to_c_int(self) -> c_int {
+        (match self {
+            LeaseType::Read => ffi::F_RDLCK,
+            LeaseType::Write => ffi::F_WRLCK,
+            LeaseType::Unlock => ffi::F_UNLCK,
+        }) as c_int
+    }
+}
+
+/// The expected lifetime of data written to a file, for `F_SET_RW_HINT`.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RwHint {
+    WriteLifeNotSet = 0,
+    WriteLifeNone = 1,
+    WriteLifeShort = 2,
+    WriteLifeMedium = 3,
+    WriteLifeLong = 4,
+    WriteLifeExtreme = 5,
+}
+
+#[cfg(target_os = "linux")]
+impl RwHint {
+    fn from_u64(hint: u64) -> Result<RwHint> {
+        use self::RwHint::*;
+
+        match hint {
+            0 => Ok(WriteLifeNotSet),
+            1 => Ok(WriteLifeNone),
+            2 => Ok(WriteLifeShort),
+            3 => Ok(WriteLifeMedium),
+            4 => Ok(WriteLifeLong),
+            5 => Ok(WriteLifeExtreme),
+            _ => Err(Error::invalid_argument()),
+        }
+    }
+}
+
+#[inline]
+fn check_fcntl(res: c_int) -> Result<c_int> {
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(res)
+}
+
+pub fn fcntl(fd: RawFd, arg: FcntlArg) -> Result<FcntlRet> {
     use self::FcntlArg::*;
 
-    let res = unsafe {
+    let ret = unsafe {
         match arg {
-            F_DUPFD(rawfd) => ffi::fcntl(fd, ffi::F_DUPFD, rawfd),
-            F_DUPFD_CLOEXEC(rawfd) => ffi::fcntl(fd, ffi::F_DUPFD_CLOEXEC, rawfd),
-            F_GETFD => ffi::fcntl(fd, ffi::F_GETFD),
-            F_SETFD(flag) => ffi::fcntl(fd, ffi::F_SETFD, flag.bits()),
-            F_GETFL => ffi::fcntl(fd, ffi::F_GETFL),
-            F_SETFL(flag) => ffi::fcntl(fd, ffi::F_SETFL, flag.bits()),
-            F_SETLK(flock) => ffi::fcntl(fd, ffi::F_SETLK, flock),
-            F_SETLKW(flock) => ffi::fcntl(fd, ffi::F_SETLKW, flock),
-            F_GETLK(flock) => ffi::fcntl(fd, ffi::F_GETLK, flock),
+            F_DUPFD(rawfd) => FcntlRet::Fd(try!(check_fcntl(ffi::fcntl(fd, ffi::F_DUPFD, rawfd)))),
+            F_DUPFD_CLOEXEC(rawfd) => FcntlRet::Fd(try!(check_fcntl(ffi::fcntl(fd, ffi::F_DUPFD_CLOEXEC, rawfd)))),
+            F_GETFD => FcntlRet::FdFlag(FdFlag::from_bits_truncate(try!(check_fcntl(ffi::fcntl(fd, ffi::F_GETFD))))),
+            F_SETFD(flag) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETFD, flag.bits())));
+                FcntlRet::Done
+            }
+            F_GETFL => FcntlRet::OFlag(OFlag::from_bits_truncate(try!(check_fcntl(ffi::fcntl(fd, ffi::F_GETFL))))),
+            F_SETFL(flag) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETFL, flag.bits())));
+                FcntlRet::Done
+            }
+            F_SETLK(flock) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETLK, flock)));
+                FcntlRet::Done
+            }
+            F_SETLKW(flock) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETLKW, flock)));
+                FcntlRet::Done
+            }
+            F_GETLK(flock) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_GETLK, flock)));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_OFD_SETLK(flock) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_OFD_SETLK, flock)));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_OFD_SETLKW(flock) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_OFD_SETLKW, flock)));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_OFD_GETLK(flock) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_OFD_GETLK, flock)));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_SETOWN(pid) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETOWN, pid)));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_GETOWN => {
+                // A process group owner is returned negated, so only errno can
+                // tell an error apart from process group 1.
+                Errno::clear();
+                let res = ffi::fcntl(fd, ffi::F_GETOWN);
+                if res == -1 && Errno::last() != Errno::UnknownErrno {
+                    return Err(Error::Sys(Errno::last()));
+                }
+                FcntlRet::Owner(res)
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_SETOWN_EX(owner) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETOWN_EX, &owner.to_ffi())));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_GETOWN_EX => {
+                let mut owner = ffi::f_owner_ex::default();
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_GETOWN_EX, &mut owner)));
+                FcntlRet::OwnerEx(try!(FOwner::from_ffi(owner)))
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_SETSIG(signal) => {
+                let signum = signal.map_or(0, |signal| signal.as_raw());
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETSIG, signum)));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_GETSIG => {
+                let signum = try!(check_fcntl(ffi::fcntl(fd, ffi::F_GETSIG)));
+                FcntlRet::Signal(match signum {
+                    0 => None,
+                    signum => Some(try!(Signal::try_from(signum))),
+                })
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_SETLEASE(lease) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETLEASE, lease.to_c_int())));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_GETLEASE => FcntlRet::Lease(try!(LeaseType::from_c_int(try!(check_fcntl(ffi::fcntl(fd, ffi::F_GETLEASE)))))),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_NOTIFY(flag) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_NOTIFY, flag.bits())));
+                FcntlRet::Done
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_SETPIPE_SZ(size) => FcntlRet::PipeSize(try!(check_fcntl(ffi::fcntl(fd, ffi::F_SETPIPE_SZ, size)))),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            F_GETPIPE_SZ => FcntlRet::PipeSize(try!(check_fcntl(ffi::fcntl(fd, ffi::F_GETPIPE_SZ)))),
             #[cfg(target_os = "linux")]
-            F_ADD_SEALS(flag) => ffi::fcntl(fd, ffi::F_ADD_SEALS, flag.bits()),
+            F_ADD_SEALS(flag) => {
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_ADD_SEALS, flag.bits())));
+                FcntlRet::Done
+            }
             #[cfg(target_os = "linux")]
-            F_GET_SEALS => ffi::fcntl(fd, ffi::F_GET_SEALS),
-            #[cfg(any(target_os = "linux", target_os = "android"))]
-            _ => unimplemented!()
+            F_GET_SEALS => FcntlRet::SealFlag(SealFlag::from_bits_truncate(try!(check_fcntl(ffi::fcntl(fd, ffi::F_GET_SEALS))))),
+            #[cfg(target_os = "linux")]
+            F_SET_RW_HINT(hint) => {
+                let hint = hint as u64;
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_SET_RW_HINT, &hint)));
+                FcntlRet::Done
+            }
+            #[cfg(target_os = "linux")]
+            F_GET_RW_HINT => {
+                let mut hint: u64 = 0;
+                try!(check_fcntl(ffi::fcntl(fd, ffi::F_GET_RW_HINT, &mut hint)));
+                FcntlRet::RwHint(try!(RwHint::from_u64(hint)))
+            }
         }
     };
 
+    Ok(ret)
+}
+
+/// 64-bit file offset used by `splice` and `copy_file_range`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub type loff_t = i64;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn offset_ptr(off: Option<&mut loff_t>) -> *mut loff_t {
+    off.map(|off| off as *mut loff_t).unwrap_or(ptr::null_mut())
+}
+
+/// Moves up to `len` bytes between two descriptors without copying through
+/// userspace. One of them must be a pipe; the other one's offset is read from
+/// and updated in its `off_*` argument if given, or is its file offset otherwise.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/splice.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn splice(fd_in: RawFd, off_in: Option<&mut loff_t>,
+              fd_out: RawFd, off_out: Option<&mut loff_t>,
+              len: usize, flags: SpliceFFlags) -> Result<usize> {
+    let res = unsafe {
+        ffi::splice(fd_in, offset_ptr(off_in), fd_out, offset_ptr(off_out),
+                    len as size_t, flags.bits())
+    };
+
     if res < 0 {
         return Err(Error::Sys(Errno::last()));
     }
 
-    Ok(res)
+    Ok(res as usize)
+}
+
+/// Duplicates up to `len` bytes from the pipe `fd_in` into the pipe `fd_out`,
+/// without consuming them.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/tee.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn tee(fd_in: RawFd, fd_out: RawFd, len: usize, flags: SpliceFFlags) -> Result<usize> {
+    let res = unsafe { ffi::tee(fd_in, fd_out, len as size_t, flags.bits()) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(res as usize)
+}
+
+/// Maps the user memory described by `iov` into the pipe `fd`.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/vmsplice.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn vmsplice(fd: RawFd, iov: &[IoVec<&[u8]>], flags: SpliceFFlags) -> Result<usize> {
+    let res = unsafe { ffi::vmsplice(fd, iov.as_ptr(), iov.len() as size_t, flags.bits()) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(res as usize)
+}
+
+/// Copies up to `len` bytes between two regular files inside the kernel.
+/// Offsets behave as for `splice`.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/copy_file_range.2.html)
+#[cfg(target_os = "linux")]
+pub fn copy_file_range(fd_in: RawFd, off_in: Option<&mut loff_t>,
+                       fd_out: RawFd, off_out: Option<&mut loff_t>,
+                       len: usize) -> Result<usize> {
+    let res = unsafe {
+        ffi::copy_file_range(fd_in, offset_ptr(off_in), fd_out, offset_ptr(off_out),
+                             len as size_t, 0)
+    };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(res as usize)
 }
 
 pub enum FlockArg {
@@ -194,9 +727,223 @@ pub fn flock(fd: RawFd, arg: FlockArg) -> Result<()> {
     Ok(())
 }
 
+/// Whether a lock may be shared with other readers or is held exclusively.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockKind {
+    Shared,
+    Exclusive,
+}
+
+/// A range of bytes to lock. A `len` of `0` extends the range to the end of
+/// the file, however far it grows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LockRange {
+    pub start: off_t,
+    pub len: off_t,
+    pub whence: Whence,
+}
+
+impl LockRange {
+    pub fn new(start: off_t, len: off_t, whence: Whence) -> LockRange {
+        LockRange { start: start, len: len, whence: whence }
+    }
+
+    /// The whole file, including any bytes appended later.
+    pub fn whole_file() -> LockRange {
+        LockRange::new(0, 0, Whence::SeekSet)
+    }
+
+    /// Returns the same range with `start` relative to the start of the file,
+    /// as it would be resolved for a lock taken on `fd` now.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn resolve(&self, fd: RawFd) -> Result<LockRange> {
+        use sys::stat::fstat;
+        use unistd::lseek;
+
+        let base = match self.whence {
+            Whence::SeekSet => return Ok(*self),
+            Whence::SeekCur => try!(lseek(fd, 0, Whence::SeekCur)),
+            Whence::SeekEnd => try!(fstat(fd)).st_size,
+            _ => return Err(Error::invalid_argument()),
+        };
+
+        match base.checked_add(self.start) {
+            Some(start) if start >= 0 => Ok(LockRange::new(start, self.len, Whence::SeekSet)),
+            Some(_) => Err(Error::invalid_argument()),
+            None => Err(Error::Sys(Errno::EOVERFLOW)),
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn to_flock(&self, l_type: c_short) -> flock {
+        flock {
+            l_type: l_type,
+            l_whence: self.whence as c_short,
+            l_start: self.start,
+            l_len: self.len,
+            .. flock::default()
+        }
+    }
+}
+
+/// A whole-file `flock` lock, released when dropped.
+///
+/// The lock belongs to the open file description, so it is shared with
+/// duplicated descriptors and released early if any of them unlocks it.
+#[derive(Debug)]
+pub struct FlockGuard<'a> {
+    fd: BorrowedFd<'a>,
+}
+
+impl<'a> FlockGuard<'a> {
+    /// Locks the file, blocking until the lock can be taken.
+    pub fn lock<F: AsRawFd>(fd: &'a F, kind: LockKind) -> Result<FlockGuard<'a>> {
+        let arg = match kind {
+            LockKind::Shared => FlockArg::LockShared,
+            LockKind::Exclusive => FlockArg::LockExclusive,
+        };
+        FlockGuard::acquire(fd, arg)
+    }
+
+    /// Locks the file, failing with `EWOULDBLOCK` if a conflicting lock is held.
+    pub fn try_lock<F: AsRawFd>(fd: &'a F, kind: LockKind) -> Result<FlockGuard<'a>> {
+        let arg = match kind {
+            LockKind::Shared => FlockArg::LockSharedNonblock,
+            LockKind::Exclusive => FlockArg::LockExclusiveNonblock,
+        };
+        FlockGuard::acquire(fd, arg)
+    }
+
+    fn acquire<F: AsRawFd>(fd: &'a F, arg: FlockArg) -> Result<FlockGuard<'a>> {
+        try!(flock(fd.as_raw_fd(), arg));
+        Ok(FlockGuard { fd: unsafe { BorrowedFd::borrow_raw(fd.as_raw_fd()) } })
+    }
+
+    /// Releases the lock, reporting any error that dropping the guard would ignore.
+    pub fn unlock(self) -> Result<()> {
+        let res = flock(self.fd.as_raw_fd(), FlockArg::Unlock);
+        mem::forget(self);
+        res
+    }
+}
+
+impl<'a> Drop for FlockGuard<'a> {
+    fn drop(&mut self) {
+        let _ = flock(self.fd.as_raw_fd(), FlockArg::Unlock);
+    }
+}
+
+/// An open file description (OFD) record lock over a byte range, released
+/// when dropped.
+///
+/// A range relative to the current offset or the end of the file is resolved
+/// to an absolute one when the lock is taken, so the same bytes are unlocked
+/// even if the offset moves or the file grows in the meantime.
+///
+/// Unlike classic `F_SETLK` locks, OFD locks are owned by the open file
+/// description rather than the process, so they are not released when an
+/// unrelated descriptor for the same file is closed, and they conflict
+/// between descriptors opened separately within the same process.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Debug)]
+pub struct OfdLockGuard<'a> {
+    fd: BorrowedFd<'a>,
+    range: LockRange,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl<'a> OfdLockGuard<'a> {
+    /// Locks `range`, blocking until the lock can be taken (`F_OFD_SETLKW`).
+    pub fn lock<F: AsRawFd>(fd: &'a F, kind: LockKind, range: LockRange) -> Result<OfdLockGuard<'a>> {
+        let range = try!(range.resolve(fd.as_raw_fd()));
+        let lock = range.to_flock(lock_type(kind));
+        try!(fcntl(fd.as_raw_fd(), FcntlArg::F_OFD_SETLKW(&lock)));
+        Ok(OfdLockGuard::new(fd, range))
+    }
+
+    /// Locks `range`, failing with `EAGAIN` if a conflicting lock is held (`F_OFD_SETLK`).
+    pub fn try_lock<F: AsRawFd>(fd: &'a F, kind: LockKind, range: LockRange) -> Result<OfdLockGuard<'a>> {
+        let range = try!(range.resolve(fd.as_raw_fd()));
+        let lock = range.to_flock(lock_type(kind));
+        try!(fcntl(fd.as_raw_fd(), FcntlArg::F_OFD_SETLK(&lock)));
+        Ok(OfdLockGuard::new(fd, range))
+    }
+
+    fn new<F: AsRawFd>(fd: &'a F, range: LockRange) -> OfdLockGuard<'a> {
+        OfdLockGuard {
+            fd: unsafe { BorrowedFd::borrow_raw(fd.as_raw_fd()) },
+            range: range,
+        }
+    }
+
+    /// The locked range, relative to the start of the file.
+    pub fn range(&self) -> LockRange {
+        self.range
+    }
+
+    /// Releases the lock, reporting any error that dropping the guard would ignore.
+    pub fn unlock(self) -> Result<()> {
+        let res = self.release();
+        mem::forget(self);
+        res
+    }
+
+    fn release(&self) -> Result<()> {
+        let lock = self.range.to_flock(ffi::F_UNLCK);
+        fcntl(self.fd.as_raw_fd(), FcntlArg::F_OFD_SETLK(&lock)).map(|_| ())
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl<'a> Drop for OfdLockGuard<'a> {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+/// A lock that prevents a requested lock from being taken, as reported by `ofd_getlk`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LockConflict {
+    pub kind: LockKind,
+    /// The conflicting range, relative to the start of the file.
+    pub range: LockRange,
+    /// The process holding a classic record lock, or `None` for OFD locks,
+    /// which have no single owning process.
+    pub pid: Option<pid_t>,
+}
+
+/// Checks whether a `kind` lock over `range` could be taken, returning the
+/// first conflicting lock if not (`F_OFD_GETLK`).
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn ofd_getlk<F: AsRawFd>(fd: &F, kind: LockKind, range: LockRange) -> Result<Option<LockConflict>> {
+    let mut lock = range.to_flock(lock_type(kind));
+    try!(fcntl(fd.as_raw_fd(), FcntlArg::F_OFD_GETLK(&mut lock)));
+
+    let kind = match lock.l_type {
+        ffi::F_UNLCK => return Ok(None),
+        ffi::F_RDLCK => LockKind::Shared,
+        _ => LockKind::Exclusive,
+    };
+
+    Ok(Some(LockConflict {
+        kind: kind,
+        range: LockRange::new(lock.l_start, lock.l_len, Whence::SeekSet),
+        pid: if lock.l_pid > 0 { Some(lock.l_pid) } else { None },
+    }))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn lock_type(kind: LockKind) -> c_short {
+    match kind {
+        LockKind::Shared => ffi::F_RDLCK,
+        LockKind::Exclusive => ffi::F_WRLCK,
+    }
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod consts {
-    use libc::c_int;
+    use libc::{c_int, c_uint};
 
     bitflags!(
         flags OFlag: c_int {
@@ -239,6 +986,61 @@ mod consts {
         }
     );
 
+    /// Special value for the directory argument of the `*at` functions,
+    /// resolving paths relative to the current working directory.
+    pub const AT_FDCWD: c_int = -100;
+
+    bitflags!(
+        flags AtFlags: c_int {
+            const AT_SYMLINK_NOFOLLOW = 0x0100,
+            const AT_REMOVEDIR        = 0x0200,
+            const AT_EACCESS          = 0x0200,
+            const AT_SYMLINK_FOLLOW   = 0x0400,
+            const AT_NO_AUTOMOUNT     = 0x0800,
+            const AT_EMPTY_PATH       = 0x1000,
+        }
+    );
+
+    bitflags!(
+        flags DnotifyFlag: c_int {
+            const DN_ACCESS    = 0x00000001,
+            const DN_MODIFY    = 0x00000002,
+            const DN_CREATE    = 0x00000004,
+            const DN_DELETE    = 0x00000008,
+            const DN_RENAME    = 0x00000010,
+            const DN_ATTRIB    = 0x00000020,
+            const DN_MULTISHOT = 0x80000000u32 as c_int,
+        }
+    );
+
+    bitflags!(
+        flags SpliceFFlags: c_uint {
+            const SPLICE_F_MOVE     = 1,
+            const SPLICE_F_NONBLOCK = 2,
+            const SPLICE_F_MORE     = 4,
+            const SPLICE_F_GIFT     = 8,
+        }
+    );
+
+    bitflags!(
+        flags ResolveFlag: u64 {
+            const RESOLVE_NO_XDEV       = 0x01,
+            const RESOLVE_NO_MAGICLINKS = 0x02,
+            const RESOLVE_NO_SYMLINKS   = 0x04,
+            const RESOLVE_BENEATH       = 0x08,
+            const RESOLVE_IN_ROOT       = 0x10,
+            const RESOLVE_CACHED        = 0x20,
+        }
+    );
+
+    bitflags!(
+        flags RenameFlags: c_int {
+            const RENAME_NOREPLACE = 1,
+            const RENAME_EXCHANGE  = 2,
+            const RENAME_WHITEOUT  = 4,
+        }
+    );
+
 }
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
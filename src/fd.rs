@@ -0,0 +1,113 @@
+//! Owned and borrowed file descriptors
+//!
+//! Every function in this crate that creates a file descriptor returns a bare
+//! `RawFd`, which must be closed manually. Each of them also comes with an
+//! `_owned` variant returning an `Fd` instead, which closes the descriptor when
+//! dropped, so early returns through `try!` no longer leak it. The exception is
+//! `signalfd`, which can also update an existing descriptor; use `SignalFd` for
+//! an owned one.
+use Result;
+use unistd;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd, FromRawFd};
+
+/// An owned file descriptor, closed when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use nix::unistd::pipe2_owned;
+/// use nix::fcntl::O_CLOEXEC;
+///
+/// let (reader, writer) = pipe2_owned(O_CLOEXEC).unwrap();
+/// drop(writer); // closes the write end
+/// # drop(reader);
+/// ```
+pub struct Fd(RawFd);
+
+impl Fd {
+    /// Borrows the descriptor for the lifetime of `self`.
+    #[inline]
+    pub fn borrow(&self) -> BorrowedFd {
+        BorrowedFd { fd: self.0, lifetime: PhantomData }
+    }
+
+    /// Creates a new owned descriptor referring to the same open file, via `dup`.
+    pub fn try_clone(&self) -> Result<Fd> {
+        unistd::dup(self.0).map(Fd)
+    }
+
+    /// Closes the descriptor, reporting any error that `close` returns.
+    ///
+    /// Dropping an `Fd` closes it as well, but silently ignores errors.
+    pub fn close(self) -> Result<()> {
+        unistd::close(self.into_raw_fd())
+    }
+}
+
+impl Drop for Fd {
+    fn drop(&mut self) {
+        let _ = unistd::close(self.0);
+    }
+}
+
+impl AsRawFd for Fd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+impl IntoRawFd for Fd {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.0;
+        mem::forget(self);
+        fd
+    }
+}
+
+impl FromRawFd for Fd {
+    unsafe fn from_raw_fd(fd: RawFd) -> Fd {
+        Fd(fd)
+    }
+}
+
+impl fmt::Debug for Fd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fd({})", self.0)
+    }
+}
+
+/// A file descriptor borrowed from an `Fd` (or from anything else that keeps it
+/// open for `'a`). It is never closed on drop.
+#[derive(Clone, Copy)]
+pub struct BorrowedFd<'a> {
+    fd: RawFd,
+    lifetime: PhantomData<&'a Fd>,
+}
+
+impl<'a> BorrowedFd<'a> {
+    /// Borrows a raw descriptor. The caller must ensure it stays open for `'a`.
+    #[inline]
+    pub unsafe fn borrow_raw(fd: RawFd) -> BorrowedFd<'a> {
+        BorrowedFd { fd: fd, lifetime: PhantomData }
+    }
+
+    /// Creates a new owned descriptor referring to the same open file, via `dup`.
+    pub fn to_owned(&self) -> Result<Fd> {
+        unistd::dup(self.fd).map(Fd)
+    }
+}
+
+impl<'a> AsRawFd for BorrowedFd<'a> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl<'a> fmt::Debug for BorrowedFd<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BorrowedFd({})", self.fd)
+    }
+}
@@ -21,9 +21,11 @@ extern crate nix_test as nixtest;
 // Re-export some libc constants
 pub use libc::{c_int, c_void};
 
+pub mod dir;
 pub mod errno;
 pub mod features;
 pub mod fcntl;
+pub mod fd;
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub mod mount;
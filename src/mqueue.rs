@@ -4,9 +4,11 @@
 
 use {Error, Result, from_ffi};
 use errno::Errno;
+use fd::Fd;
 
 use libc::{c_int, c_long, c_char, size_t, mode_t, strlen};
 use std::ffi::CString;
+use std::os::unix::io::FromRawFd;
 use sys::stat::Mode;
 
 pub use self::consts::*;
@@ -87,6 +89,15 @@ pub fn mq_open(name: &CString, oflag: MQ_OFlag, mode: Mode, attr: &MqAttr) -> Re
     Ok(res)
 }
 
+/// Like `mq_open`, but returns an owned `Fd` that is closed on drop.
+///
+/// On Linux a message queue descriptor is a regular file descriptor, so it can
+/// be closed with `close` and polled like any other.
+#[inline]
+pub fn mq_open_owned(name: &CString, oflag: MQ_OFlag, mode: Mode, attr: &MqAttr) -> Result<Fd> {
+    mq_open(name, oflag, mode, attr).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 pub fn mq_unlink(name: &CString) -> Result<()> {
     let res = unsafe { ffi::mq_unlink(name.as_ptr()) };
     from_ffi(res)
@@ -1,7 +1,8 @@
 use {Error, Result, from_ffi};
 use errno::Errno;
+use fd::Fd;
 use libc::c_int;
-use std::os::unix::io::RawFd;
+use std::os::unix::io::{RawFd, FromRawFd};
 
 mod ffi {
     use libc::{c_int};
@@ -83,6 +84,12 @@ pub fn epoll_create() -> Result<RawFd> {
     Ok(res)
 }
 
+/// Like `epoll_create`, but returns an owned `Fd` that is closed on drop.
+#[inline]
+pub fn epoll_create_owned() -> Result<Fd> {
+    epoll_create().map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 #[inline]
 pub fn epoll_ctl(epfd: RawFd, op: EpollOp, fd: RawFd, event: &EpollEvent) -> Result<()> {
     let res = unsafe { ffi::epoll_ctl(epfd, op as c_int, fd, event as *const EpollEvent) };
@@ -7,7 +7,8 @@ use errno::Errno;
 use libc::{timespec, time_t, c_int, c_long, uintptr_t};
 #[cfg(target_os = "netbsd")]
 use libc::{timespec, time_t, c_long, uintptr_t, size_t};
-use std::os::unix::io::RawFd;
+use fd::Fd;
+use std::os::unix::io::{RawFd, FromRawFd};
 use std::ptr;
 
 pub use self::ffi::kevent as KEvent;
@@ -284,6 +285,11 @@ pub fn kqueue() -> Result<RawFd> {
     Ok(res)
 }
 
+/// Like `kqueue`, but returns an owned `Fd` that is closed on drop.
+pub fn kqueue_owned() -> Result<Fd> {
+    kqueue().map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 pub fn kevent(kq: RawFd,
               changelist: &[KEvent],
               eventlist: &mut [KEvent],
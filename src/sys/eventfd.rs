@@ -1,6 +1,7 @@
 use libc;
-use std::os::unix::io::RawFd;
+use std::os::unix::io::{RawFd, FromRawFd};
 use {Error, Result};
+use fd::Fd;
 
 bitflags!(
     flags EventFdFlag: libc::c_int {
@@ -29,3 +30,8 @@ pub fn eventfd(initval: usize, flags: EventFdFlag) -> Result<RawFd> {
         Ok(res as RawFd)
     }
 }
+
+/// Like `eventfd`, but returns an owned `Fd` that is closed on drop.
+pub fn eventfd_owned(initval: usize, flags: EventFdFlag) -> Result<Fd> {
+    eventfd(initval, flags).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
@@ -0,0 +1,547 @@
+//! Monitoring filesystem events with `fanotify`.
+//!
+//! Unlike `inotify`, fanotify can watch whole mounts and filesystems, passes
+//! an open descriptor for the accessed file along with each event, and can ask
+//! the listener for permission before an access completes. Most of it requires
+//! `CAP_SYS_ADMIN`.
+//!
+//! [Further reading](http://man7.org/linux/man-pages/man7/fanotify.7.html)
+use libc::{c_uint, pid_t};
+use {Error, Result, NixPath};
+use errno::Errno;
+use fcntl::{OFlag, at_rawfd};
+use fd::Fd;
+use unistd;
+
+use std::ffi::{OsStr, OsString};
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd, FromRawFd};
+use std::ptr;
+use std::slice;
+
+mod ffi {
+    use libc::{c_char, c_int, c_uint};
+
+    #[repr(C)]
+    pub struct fanotify_event_metadata {
+        pub event_len: u32,
+        pub vers: u8,
+        pub reserved: u8,
+        pub metadata_len: u16,
+        pub mask: u64,
+        pub fd: i32,
+        pub pid: i32,
+    }
+
+    #[repr(C)]
+    pub struct fanotify_event_info_header {
+        pub info_type: u8,
+        pub pad: u8,
+        pub len: u16,
+    }
+
+    #[repr(C)]
+    pub struct fanotify_event_info_fid {
+        pub hdr: fanotify_event_info_header,
+        pub fsid: [i32; 2],
+        // followed by a `struct file_handle`
+        pub handle_bytes: u32,
+        pub handle_type: i32,
+    }
+
+    #[repr(C)]
+    pub struct fanotify_response {
+        pub fd: i32,
+        pub response: u32,
+    }
+
+    pub const FANOTIFY_METADATA_VERSION: u8 = 3;
+    pub const FAN_NOFD: i32 = -1;
+
+    pub const FAN_ALLOW: u32 = 0x01;
+    pub const FAN_DENY:  u32 = 0x02;
+    pub const FAN_AUDIT: u32 = 0x10;
+
+    pub const FAN_EVENT_INFO_TYPE_FID:           u8 = 1;
+    pub const FAN_EVENT_INFO_TYPE_DFID_NAME:     u8 = 2;
+    pub const FAN_EVENT_INFO_TYPE_DFID:          u8 = 3;
+    pub const FAN_EVENT_INFO_TYPE_OLD_DFID_NAME: u8 = 10;
+    pub const FAN_EVENT_INFO_TYPE_NEW_DFID_NAME: u8 = 12;
+
+    extern {
+        pub fn fanotify_init(flags: c_uint, event_f_flags: c_uint) -> c_int;
+        pub fn fanotify_mark(fanotify_fd: c_int, flags: c_uint, mask: u64,
+                             dirfd: c_int, pathname: *const c_char) -> c_int;
+    }
+}
+
+bitflags!(
+    flags InitFlags: c_uint {
+        const FAN_CLOEXEC           = 0x00000001,
+        const FAN_NONBLOCK          = 0x00000002,
+
+        const FAN_UNLIMITED_QUEUE   = 0x00000010,
+        const FAN_UNLIMITED_MARKS   = 0x00000020,
+        const FAN_ENABLE_AUDIT      = 0x00000040,
+
+        // Event reporting formats
+        const FAN_REPORT_PIDFD      = 0x00000080,
+        const FAN_REPORT_TID        = 0x00000100,
+        const FAN_REPORT_FID        = 0x00000200,
+        const FAN_REPORT_DIR_FID    = 0x00000400,
+        const FAN_REPORT_NAME       = 0x00000800,
+        const FAN_REPORT_TARGET_FID = 0x00001000,
+        const FAN_REPORT_DFID_NAME  = 0x00000c00, // FAN_REPORT_DIR_FID | FAN_REPORT_NAME
+    }
+);
+
+bitflags!(
+    flags MarkFlags: c_uint {
+        const FAN_MARK_ADD                 = 0x00000001,
+        const FAN_MARK_REMOVE              = 0x00000002,
+        const FAN_MARK_DONT_FOLLOW         = 0x00000004,
+        const FAN_MARK_ONLYDIR             = 0x00000008,
+        const FAN_MARK_IGNORED_MASK        = 0x00000020,
+        const FAN_MARK_IGNORED_SURV_MODIFY = 0x00000040,
+        const FAN_MARK_FLUSH               = 0x00000080,
+        const FAN_MARK_EVICTABLE           = 0x00000200,
+        const FAN_MARK_IGNORE              = 0x00000400,
+    }
+);
+
+bitflags!(
+    flags MaskFlags: u64 {
+        const FAN_ACCESS         = 0x00000001,
+        const FAN_MODIFY         = 0x00000002,
+        const FAN_ATTRIB         = 0x00000004,
+        const FAN_CLOSE_WRITE    = 0x00000008,
+        const FAN_CLOSE_NOWRITE  = 0x00000010,
+        const FAN_OPEN           = 0x00000020,
+        const FAN_MOVED_FROM     = 0x00000040,
+        const FAN_MOVED_TO       = 0x00000080,
+        const FAN_CREATE         = 0x00000100,
+        const FAN_DELETE         = 0x00000200,
+        const FAN_DELETE_SELF    = 0x00000400,
+        const FAN_MOVE_SELF      = 0x00000800,
+        const FAN_OPEN_EXEC      = 0x00001000,
+        const FAN_Q_OVERFLOW     = 0x00004000,
+        const FAN_FS_ERROR       = 0x00008000,
+        const FAN_OPEN_PERM      = 0x00010000,
+        const FAN_ACCESS_PERM    = 0x00020000,
+        const FAN_OPEN_EXEC_PERM = 0x00040000,
+        const FAN_EVENT_ON_CHILD = 0x08000000,
+        const FAN_RENAME         = 0x10000000,
+        const FAN_ONDIR          = 0x40000000,
+
+        const FAN_CLOSE          = 0x00000018, // FAN_CLOSE_WRITE | FAN_CLOSE_NOWRITE
+        const FAN_MOVE           = 0x000000c0, // FAN_MOVED_FROM | FAN_MOVED_TO
+    }
+);
+
+/// When a group is notified, relative to the other groups, which decides
+/// what it can see and whether it can receive permission events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Class {
+    /// After the access, with no permission events (`FAN_CLASS_NOTIF`).
+    Notif,
+    /// Once the file contents are final, e.g. for scanning them
+    /// (`FAN_CLASS_CONTENT`).
+    Content,
+    /// Before the file contents are final, e.g. for filling them in from
+    /// storage (`FAN_CLASS_PRE_CONTENT`).
+    PreContent,
+}
+
+impl Class {
+    fn bits(&self) -> c_uint {
+        match *self {
+            Class::Notif => 0x00000000,
+            Class::Content => 0x00000004,
+            Class::PreContent => 0x00000008,
+        }
+    }
+}
+
+/// What a mark applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkTarget {
+    /// The file or directory itself (`FAN_MARK_INODE`).
+    Inode,
+    /// The whole mount containing it (`FAN_MARK_MOUNT`).
+    Mount,
+    /// The whole filesystem containing it (`FAN_MARK_FILESYSTEM`).
+    Filesystem,
+}
+
+impl MarkTarget {
+    fn bits(&self) -> c_uint {
+        match *self {
+            MarkTarget::Inode => 0x00000000,
+            MarkTarget::Mount => 0x00000010,
+            MarkTarget::Filesystem => 0x00000100,
+        }
+    }
+}
+
+/// The answer to a permission event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Response {
+    /// Let the access proceed (`FAN_ALLOW`).
+    Allow,
+    /// Fail the access with `EPERM` (`FAN_DENY`).
+    Deny,
+}
+
+impl Response {
+    fn bits(&self) -> u32 {
+        match *self {
+            Response::Allow => ffi::FAN_ALLOW,
+            Response::Deny => ffi::FAN_DENY,
+        }
+    }
+}
+
+/// The kind of file identifier carried by a `FidInfo`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FidKind {
+    /// The object the event happened on (`FAN_REPORT_FID`).
+    Fid,
+    /// The directory containing the object (`FAN_REPORT_DIR_FID`).
+    Dfid,
+    /// The directory and name of the object (`FAN_REPORT_DFID_NAME`).
+    DfidName,
+    /// The old directory and name in a `FAN_RENAME` event.
+    OldDfidName,
+    /// The new directory and name in a `FAN_RENAME` event.
+    NewDfidName,
+}
+
+/// A file identifier record, reported instead of an open descriptor when the
+/// group was created with one of the `FAN_REPORT_*FID` flags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FidInfo {
+    pub kind: FidKind,
+    /// The id of the filesystem containing the object, as in `statfs`.
+    pub fsid: [i32; 2],
+    /// The type of `handle`, for use with `open_by_handle_at`.
+    pub handle_type: i32,
+    /// The opaque file handle bytes.
+    pub handle: Vec<u8>,
+    /// The name of the entry inside the directory, for the `*Name` kinds.
+    pub name: Option<OsString>,
+}
+
+/// An information record following the event metadata.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FanotifyInfo {
+    Fid(FidInfo),
+    /// A record type this crate does not parse, with its raw body.
+    Other { info_type: u8, data: Vec<u8> },
+}
+
+/// A single event read from a `Fanotify` group.
+#[derive(Debug)]
+pub struct FanotifyEvent {
+    pub mask: MaskFlags,
+    /// An open descriptor for the accessed file, or `None` for overflow events
+    /// and groups reporting file identifiers. It is closed when dropped.
+    pub fd: Option<Fd>,
+    /// The process (or thread, with `FAN_REPORT_TID`) that caused the event.
+    pub pid: pid_t,
+    pub info: Vec<FanotifyInfo>,
+}
+
+impl FanotifyEvent {
+    /// Whether the event must be answered with `Fanotify::write_response`.
+    pub fn is_permission(&self) -> bool {
+        is_permission(self.mask)
+    }
+}
+
+fn is_permission(mask: MaskFlags) -> bool {
+    mask.intersects(FAN_OPEN_PERM | FAN_ACCESS_PERM | FAN_OPEN_EXEC_PERM)
+}
+
+/// An owned fanotify group, closed when dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use nix::sys::fanotify::*;
+/// use nix::fcntl::{O_RDONLY, O_CLOEXEC};
+///
+/// let group = Fanotify::init(Class::Content, FAN_CLOEXEC, O_RDONLY | O_CLOEXEC).unwrap();
+/// group.mark(FAN_MARK_ADD, MarkTarget::Mount, FAN_OPEN_PERM, None, Some("/".as_bytes())).unwrap();
+///
+/// loop {
+///     for event in group.read_events().unwrap() {
+///         if let Some(ref fd) = event.fd {
+///             group.write_response(fd, Response::Allow).unwrap();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Fanotify(Fd);
+
+impl Fanotify {
+    /// Creates a new group. `event_f_flags` are the open flags used for the
+    /// descriptors passed along with events, e.g. `O_RDONLY | O_CLOEXEC`.
+    pub fn init(class: Class, flags: InitFlags, event_f_flags: OFlag) -> Result<Fanotify> {
+        let fd = unsafe {
+            ffi::fanotify_init(class.bits() | flags.bits(), event_f_flags.bits() as c_uint)
+        };
+
+        if fd < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(Fanotify(unsafe { Fd::from_raw_fd(fd) }))
+    }
+
+    /// Adds, removes or flushes marks. The object marked is `path` relative to
+    /// `dirfd`, or `dirfd` itself if `path` is `None`.
+    pub fn mark<P: ?Sized + NixPath>(&self, flags: MarkFlags, target: MarkTarget, mask: MaskFlags,
+                                     dirfd: Option<RawFd>, path: Option<&P>) -> Result<()> {
+        let fd = self.0.as_raw_fd();
+        let flags = flags.bits() | target.bits();
+        let res = match path {
+            Some(path) => try!(path.with_nix_path(|cstr| unsafe {
+                ffi::fanotify_mark(fd, flags, mask.bits(), at_rawfd(dirfd), cstr.as_ptr())
+            })),
+            None => unsafe {
+                ffi::fanotify_mark(fd, flags, mask.bits(), at_rawfd(dirfd), ptr::null())
+            },
+        };
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(())
+    }
+
+    /// Reads the pending events, blocking until there is at least one unless
+    /// the group was created with `FAN_NONBLOCK`, in which case an empty list
+    /// is returned instead.
+    ///
+    /// Fails with `EINVAL` if the kernel reports events in a format this crate
+    /// does not know. The descriptors of all events read are closed then, and
+    /// permission events denied, so that a malformed buffer never lets an
+    /// access through unchecked.
+    pub fn read_events(&self) -> Result<Vec<FanotifyEvent>> {
+        // u64 elements keep the metadata records aligned
+        let mut buffer = [0u64; 1024];
+        let buffer = unsafe {
+            slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+        };
+        let mut events = Vec::new();
+
+        let nread = match unistd::read(self.0.as_raw_fd(), buffer) {
+            Ok(nread) => nread,
+            Err(Error::Sys(Errno::EAGAIN)) => return Ok(events),
+            Err(e) => return Err(e),
+        };
+
+        let mut offset = 0;
+        while offset < nread {
+            let rest = &buffer[offset..nread];
+            let metadata = match read_metadata(rest) {
+                Some(metadata) => metadata,
+                None => {
+                    self.discard(events, rest);
+                    return Err(Error::invalid_argument());
+                }
+            };
+            let event_len = metadata.event_len as usize;
+
+            let fd = if metadata.fd == ffi::FAN_NOFD {
+                None
+            } else {
+                Some(unsafe { Fd::from_raw_fd(metadata.fd) })
+            };
+
+            let info = parse_info(&rest[metadata.metadata_len as usize..event_len]);
+
+            events.push(FanotifyEvent {
+                mask: MaskFlags::from_bits_truncate(metadata.mask),
+                fd: fd,
+                pid: metadata.pid,
+                info: info,
+            });
+
+            offset += event_len;
+        }
+
+        Ok(events)
+    }
+
+    /// Answers a permission event about the file open as `fd`. The access
+    /// blocks until it is answered.
+    pub fn write_response<F: AsRawFd>(&self, fd: &F, response: Response) -> Result<()> {
+        self.respond(fd.as_raw_fd(), response.bits())
+    }
+
+    /// Like `write_response`, but also logs the decision to the audit
+    /// subsystem. The group must have been created with `FAN_ENABLE_AUDIT`.
+    pub fn write_audited_response<F: AsRawFd>(&self, fd: &F, response: Response) -> Result<()> {
+        self.respond(fd.as_raw_fd(), response.bits() | ffi::FAN_AUDIT)
+    }
+
+    fn respond(&self, fd: RawFd, response: u32) -> Result<()> {
+        let response = ffi::fanotify_response { fd: fd, response: response };
+        let buffer = unsafe {
+            slice::from_raw_parts(&response as *const ffi::fanotify_response as *const u8,
+                                  mem::size_of::<ffi::fanotify_response>())
+        };
+
+        try!(unistd::write(self.0.as_raw_fd(), buffer));
+        Ok(())
+    }
+
+    /// Closes the descriptors of events that cannot be returned, and denies
+    /// their permission requests so the accessing processes do not hang.
+    /// `rest` holds the events that were not parsed yet.
+    fn discard(&self, events: Vec<FanotifyEvent>, mut rest: &[u8]) {
+        for event in events {
+            if let Some(ref fd) = event.fd {
+                if event.is_permission() {
+                    let _ = self.write_response(fd, Response::Deny);
+                }
+            }
+        }
+
+        let metadata_size = mem::size_of::<ffi::fanotify_event_metadata>();
+        while rest.len() >= metadata_size {
+            let metadata: ffi::fanotify_event_metadata = unsafe { read_struct(rest) };
+
+            if metadata.fd != ffi::FAN_NOFD {
+                let fd = unsafe { Fd::from_raw_fd(metadata.fd) };
+                if is_permission(MaskFlags::from_bits_truncate(metadata.mask)) {
+                    let _ = self.write_response(&fd, Response::Deny);
+                }
+            }
+
+            // Without a usable length the following events cannot be found
+            let event_len = metadata.event_len as usize;
+            if event_len < metadata_size || event_len > rest.len() {
+                break;
+            }
+            rest = &rest[event_len..];
+        }
+    }
+}
+
+impl AsRawFd for Fanotify {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Fanotify {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+
+impl FromRawFd for Fanotify {
+    unsafe fn from_raw_fd(fd: RawFd) -> Fanotify {
+        Fanotify(Fd::from_raw_fd(fd))
+    }
+}
+
+/// Copies a `T` out of the front of `buf`, which must be long enough.
+unsafe fn read_struct<T>(buf: &[u8]) -> T {
+    assert!(buf.len() >= mem::size_of::<T>());
+    let mut value = mem::uninitialized();
+    ptr::copy_nonoverlapping(buf.as_ptr(), &mut value as *mut T as *mut u8, mem::size_of::<T>());
+    value
+}
+
+/// Reads the metadata of the event at the front of `buf`, or `None` if its
+/// version is unknown or its lengths do not fit.
+fn read_metadata(buf: &[u8]) -> Option<ffi::fanotify_event_metadata> {
+    let metadata_size = mem::size_of::<ffi::fanotify_event_metadata>();
+    if buf.len() < metadata_size {
+        return None;
+    }
+
+    let metadata: ffi::fanotify_event_metadata = unsafe { read_struct(buf) };
+    let metadata_len = metadata.metadata_len as usize;
+    let event_len = metadata.event_len as usize;
+
+    if metadata.vers != ffi::FANOTIFY_METADATA_VERSION ||
+       metadata_len < metadata_size || metadata_len > event_len || event_len > buf.len() {
+        return None;
+    }
+
+    Some(metadata)
+}
+
+fn parse_info(mut buf: &[u8]) -> Vec<FanotifyInfo> {
+    let header_size = mem::size_of::<ffi::fanotify_event_info_header>();
+    let mut info = Vec::new();
+
+    while buf.len() >= header_size {
+        let header: ffi::fanotify_event_info_header = unsafe { read_struct(buf) };
+        let len = header.len as usize;
+        if len < header_size || len > buf.len() {
+            break;
+        }
+
+        let record = &buf[..len];
+        let kind = match header.info_type {
+            ffi::FAN_EVENT_INFO_TYPE_FID => Some(FidKind::Fid),
+            ffi::FAN_EVENT_INFO_TYPE_DFID => Some(FidKind::Dfid),
+            ffi::FAN_EVENT_INFO_TYPE_DFID_NAME => Some(FidKind::DfidName),
+            ffi::FAN_EVENT_INFO_TYPE_OLD_DFID_NAME => Some(FidKind::OldDfidName),
+            ffi::FAN_EVENT_INFO_TYPE_NEW_DFID_NAME => Some(FidKind::NewDfidName),
+            _ => None,
+        };
+
+        let fid = kind.and_then(|kind| parse_fid(kind, record));
+        info.push(match fid {
+            Some(fid) => FanotifyInfo::Fid(fid),
+            None => FanotifyInfo::Other {
+                info_type: header.info_type,
+                data: record[header_size..].to_vec(),
+            },
+        });
+
+        buf = &buf[len..];
+    }
+
+    info
+}
+
+fn parse_fid(kind: FidKind, record: &[u8]) -> Option<FidInfo> {
+    let fid_size = mem::size_of::<ffi::fanotify_event_info_fid>();
+    if record.len() < fid_size {
+        return None;
+    }
+
+    let fid: ffi::fanotify_event_info_fid = unsafe { read_struct(record) };
+    let handle_end = fid_size + fid.handle_bytes as usize;
+    if handle_end > record.len() {
+        return None;
+    }
+
+    let name = match kind {
+        FidKind::DfidName | FidKind::OldDfidName | FidKind::NewDfidName => {
+            // NUL-terminated, and padded to the record alignment
+            let name = &record[handle_end..];
+            let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+            Some(OsStr::from_bytes(&name[..len]).to_os_string())
+        }
+        _ => None,
+    };
+
+    Some(FidInfo {
+        kind: kind,
+        fsid: fid.fsid,
+        handle_type: fid.handle_type,
+        handle: record[fid_size..handle_end].to_vec(),
+        name: name,
+    })
+}
@@ -0,0 +1,225 @@
+//! Monitoring filesystem events with `inotify`.
+//!
+//! An `Inotify` instance is a file descriptor that becomes readable whenever
+//! one of its watches reports an event, so it can be used with `poll::poll` or
+//! `sys::epoll` like any other descriptor when opened with `IN_NONBLOCK`.
+//!
+//! [Further reading](http://man7.org/linux/man-pages/man7/inotify.7.html)
+use libc::{c_int, uint32_t};
+use {Error, Result, NixPath};
+use errno::Errno;
+use fd::Fd;
+use unistd;
+
+use std::ffi::{OsStr, OsString};
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd, FromRawFd};
+use std::ptr;
+
+mod ffi {
+    use libc::{c_char, c_int, uint32_t};
+
+    #[repr(C)]
+    pub struct inotify_event {
+        pub wd: c_int,
+        pub mask: uint32_t,
+        pub cookie: uint32_t,
+        pub len: uint32_t,
+        // followed by `len` bytes of NUL-padded name
+    }
+
+    extern {
+        pub fn inotify_init1(flags: c_int) -> c_int;
+        pub fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: uint32_t) -> c_int;
+        pub fn inotify_rm_watch(fd: c_int, wd: c_int) -> c_int;
+    }
+}
+
+bitflags!(
+    flags InitFlags: c_int {
+        const IN_NONBLOCK = 0o00004000, // O_NONBLOCK
+        const IN_CLOEXEC  = 0o02000000, // O_CLOEXEC
+    }
+);
+
+bitflags!(
+    flags AddWatchFlags: uint32_t {
+        // Events that can be watched for
+        const IN_ACCESS        = 0x00000001,
+        const IN_MODIFY        = 0x00000002,
+        const IN_ATTRIB        = 0x00000004,
+        const IN_CLOSE_WRITE   = 0x00000008,
+        const IN_CLOSE_NOWRITE = 0x00000010,
+        const IN_OPEN          = 0x00000020,
+        const IN_MOVED_FROM    = 0x00000040,
+        const IN_MOVED_TO      = 0x00000080,
+        const IN_CREATE        = 0x00000100,
+        const IN_DELETE        = 0x00000200,
+        const IN_DELETE_SELF   = 0x00000400,
+        const IN_MOVE_SELF     = 0x00000800,
+
+        const IN_CLOSE         = 0x00000018, // IN_CLOSE_WRITE | IN_CLOSE_NOWRITE
+        const IN_MOVE          = 0x000000c0, // IN_MOVED_FROM | IN_MOVED_TO
+        const IN_ALL_EVENTS    = 0x00000fff,
+
+        // Only ever set in returned events
+        const IN_UNMOUNT       = 0x00002000,
+        const IN_Q_OVERFLOW    = 0x00004000,
+        const IN_IGNORED       = 0x00008000,
+        const IN_ISDIR         = 0x40000000,
+
+        // Options for `add_watch`
+        const IN_ONLYDIR       = 0x01000000,
+        const IN_DONT_FOLLOW   = 0x02000000,
+        const IN_EXCL_UNLINK   = 0x04000000,
+        const IN_MASK_CREATE   = 0x10000000,
+        const IN_MASK_ADD      = 0x20000000,
+        const IN_ONESHOT       = 0x80000000,
+    }
+);
+
+/// Identifies a watch added with `Inotify::add_watch`, and the events it reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WatchDescriptor(c_int);
+
+/// A single event read from an `Inotify` instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InotifyEvent {
+    /// The watch that reported the event. `IN_Q_OVERFLOW` events are not tied
+    /// to any watch and report a descriptor of -1.
+    pub wd: WatchDescriptor,
+    /// The event that occurred, along with `IN_ISDIR`, `IN_IGNORED` etc.
+    pub mask: AddWatchFlags,
+    /// Connects the `IN_MOVED_FROM` and `IN_MOVED_TO` halves of a rename.
+    pub cookie: u32,
+    /// The name of the file inside a watched directory that the event is
+    /// about, or `None` for events on the watched object itself.
+    pub name: Option<OsString>,
+}
+
+/// An owned inotify instance, closed when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use nix::sys::inotify::{Inotify, IN_CREATE, IN_DELETE, IN_NONBLOCK};
+///
+/// let inotify = Inotify::init(IN_NONBLOCK).unwrap();
+/// let wd = inotify.add_watch("/tmp".as_bytes(), IN_CREATE | IN_DELETE).unwrap();
+///
+/// for event in inotify.read_events().unwrap() {
+///     println!("{:?} {:?}", event.mask, event.name);
+/// }
+///
+/// inotify.rm_watch(wd).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Inotify(Fd);
+
+impl Inotify {
+    pub fn init(flags: InitFlags) -> Result<Inotify> {
+        let fd = unsafe { ffi::inotify_init1(flags.bits()) };
+
+        if fd < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(Inotify(unsafe { Fd::from_raw_fd(fd) }))
+    }
+
+    /// Starts watching `path` for the events in `mask`. Watching the same
+    /// inode again replaces its mask (or extends it, with `IN_MASK_ADD`) and
+    /// returns the same descriptor.
+    pub fn add_watch<P: ?Sized + NixPath>(&self, path: &P, mask: AddWatchFlags) -> Result<WatchDescriptor> {
+        let wd = try!(path.with_nix_path(|cstr| unsafe {
+            ffi::inotify_add_watch(self.0.as_raw_fd(), cstr.as_ptr(), mask.bits())
+        }));
+
+        if wd < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(WatchDescriptor(wd))
+    }
+
+    /// Removes a watch. An `IN_IGNORED` event is generated for it.
+    pub fn rm_watch(&self, wd: WatchDescriptor) -> Result<()> {
+        let res = unsafe { ffi::inotify_rm_watch(self.0.as_raw_fd(), wd.0) };
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(())
+    }
+
+    /// Reads the pending events, blocking until there is at least one unless
+    /// the instance was created with `IN_NONBLOCK`, in which case an empty list
+    /// is returned instead.
+    pub fn read_events(&self) -> Result<Vec<InotifyEvent>> {
+        // Large enough for at least one event with a maximum-length name
+        const BUFSIZ: usize = 4096;
+        let header_size = mem::size_of::<ffi::inotify_event>();
+        let mut buffer = [0u8; BUFSIZ];
+        let mut events = Vec::new();
+
+        let nread = match unistd::read(self.0.as_raw_fd(), &mut buffer) {
+            Ok(nread) => nread,
+            Err(Error::Sys(Errno::EAGAIN)) => return Ok(events),
+            Err(e) => return Err(e),
+        };
+
+        let mut offset = 0;
+        while offset + header_size <= nread {
+            let header: ffi::inotify_event = unsafe {
+                let mut header = mem::uninitialized();
+                // The byte buffer makes no alignment guarantees
+                ptr::copy_nonoverlapping(buffer[offset..].as_ptr(),
+                                         &mut header as *mut ffi::inotify_event as *mut u8,
+                                         header_size);
+                header
+            };
+
+            let name_start = offset + header_size;
+            let name_end = name_start + header.len as usize;
+            let name = if header.len == 0 {
+                None
+            } else {
+                // The name is NUL-terminated and padded with further NULs
+                let name = &buffer[name_start..name_end];
+                let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+                Some(OsStr::from_bytes(&name[..len]).to_os_string())
+            };
+
+            events.push(InotifyEvent {
+                wd: WatchDescriptor(header.wd),
+                mask: AddWatchFlags::from_bits_truncate(header.mask),
+                cookie: header.cookie,
+                name: name,
+            });
+
+            offset = name_end;
+        }
+
+        Ok(events)
+    }
+}
+
+impl AsRawFd for Inotify {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Inotify {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+
+impl FromRawFd for Inotify {
+    unsafe fn from_raw_fd(fd: RawFd) -> Inotify {
+        Inotify(Fd::from_raw_fd(fd))
+    }
+}
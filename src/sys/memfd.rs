@@ -1,6 +1,7 @@
 use libc;
-use std::os::unix::io::RawFd;
+use std::os::unix::io::{RawFd, FromRawFd};
 use {Error, Result};
+use fd::Fd;
 use std::ffi::CStr;
 
 bitflags!(
@@ -16,3 +17,8 @@ pub fn memfd_create(name: &CStr, flags: MemFdCreateFlag) -> Result<RawFd> {
     if res == -1 { Err(Error::last()) }
     else { Ok(res as RawFd) }
 }
+
+/// Like `memfd_create`, but returns an owned `Fd` that is closed on drop.
+pub fn memfd_create_owned(name: &CStr, flags: MemFdCreateFlag) -> Result<Fd> {
+    memfd_create(name, flags).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
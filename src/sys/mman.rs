@@ -1,9 +1,10 @@
 use {Error, Result, NixPath};
 use errno::Errno;
+use fd::Fd;
 use fcntl::OFlag;
 use libc::{c_void, size_t, off_t, mode_t};
 use sys::stat::Mode;
-use std::os::unix::io::RawFd;
+use std::os::unix::io::{RawFd, FromRawFd};
 
 pub use self::consts::*;
 
@@ -251,6 +252,11 @@ pub fn shm_open<P: ?Sized + NixPath>(name: &P, flag: OFlag, mode: Mode) -> Resul
     }
 }
 
+/// Like `shm_open`, but returns an owned `Fd` that is closed on drop.
+pub fn shm_open_owned<P: ?Sized + NixPath>(name: &P, flag: OFlag, mode: Mode) -> Result<Fd> {
+    shm_open(name, flag, mode).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 pub fn shm_unlink<P: ?Sized + NixPath>(name: &P) -> Result<()> {
     let ret = try!(name.with_nix_path(|cstr| {
         unsafe { ffi::shm_unlink(cstr.as_ptr()) }
@@ -13,9 +13,18 @@ pub mod eventfd;
 #[cfg(target_os = "linux")]
 pub mod memfd;
 
+#[cfg(target_os = "linux")]
+pub mod fanotify;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod inotify;
+
 #[cfg(not(any(target_os = "ios", target_os = "freebsd", target_os = "dragonfly")))]
 pub mod ioctl;
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod sendfile;
+
 pub mod signal;
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -32,11 +41,20 @@ pub mod syscall;
 #[cfg(not(target_os = "ios"))]
 pub mod termios;
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod timer;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod timerfd;
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub mod utsname;
 
 pub mod wait;
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod xattr;
+
 pub mod mman;
 
 pub mod uio;
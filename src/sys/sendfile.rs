@@ -0,0 +1,33 @@
+use std::os::unix::io::RawFd;
+use std::ptr;
+
+use libc::{off_t, size_t};
+
+use {Error, Result};
+use errno::Errno;
+
+mod ffi {
+    use libc::{c_int, off_t, size_t, ssize_t};
+
+    extern {
+        pub fn sendfile(out_fd: c_int, in_fd: c_int, offset: *mut off_t, count: size_t) -> ssize_t;
+    }
+}
+
+/// Copies up to `count` bytes from `in_fd` to `out_fd` inside the kernel.
+///
+/// If `offset` is given, reading starts there and it is updated to point past
+/// the last byte read, leaving the file offset of `in_fd` untouched. Otherwise
+/// the file offset of `in_fd` is used and updated.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/sendfile.2.html)
+pub fn sendfile(out_fd: RawFd, in_fd: RawFd, offset: Option<&mut off_t>, count: usize) -> Result<usize> {
+    let offset = offset.map(|offset| offset as *mut off_t).unwrap_or(ptr::null_mut());
+    let res = unsafe { ffi::sendfile(out_fd, in_fd, offset, count as size_t) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(res as usize)
+}
@@ -1,52 +1,380 @@
 // Portions of this file are Copyright 2014 The Rust Project Developers.
 // See http://rust-lang.org/COPYRIGHT.
 
-use libc;
+use libc::{self, c_int};
 use errno::Errno;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ptr;
+use std::str::FromStr;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use sys::time::TimeSpec;
 use {Error, Result};
 
-pub use libc::{
+/// A signal, as sent with `kill` and handled with `sigaction`.
+///
+/// The variants are re-exported at the module level, so `SIGTERM` and
+/// `Signal::SIGTERM` are the same value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Signal {
     SIGHUP,
     SIGINT,
     SIGQUIT,
     SIGILL,
+    SIGTRAP,
     SIGABRT,
+    SIGBUS,
     SIGFPE,
     SIGKILL,
+    SIGUSR1,
     SIGSEGV,
+    SIGUSR2,
     SIGPIPE,
     SIGALRM,
     SIGTERM,
-    SIGTRAP,
-    SIGIOT,
-    SIGBUS,
-    SIGSYS,
-    SIGURG,
+    #[cfg(any(all(target_os = "linux",
+                  any(target_arch = "x86",
+                      target_arch = "x86_64",
+                      target_arch = "aarch64",
+                      target_arch = "arm")),
+              target_os = "android"))]
+    SIGSTKFLT,
+    SIGCHLD,
+    SIGCONT,
     SIGSTOP,
     SIGTSTP,
-    SIGCONT,
-    SIGCHLD,
     SIGTTIN,
     SIGTTOU,
-    SIGIO,
+    SIGURG,
     SIGXCPU,
     SIGXFSZ,
     SIGVTALRM,
     SIGPROF,
     SIGWINCH,
+    SIGIO,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    SIGPWR,
+    SIGSYS,
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    SIGEMT,
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    SIGINFO,
+    /// The realtime signal `SIGRTMIN + n`. Realtime signals are queued rather
+    /// than merged, and carry a value when sent with `sigqueue` or a timer.
+    ///
+    /// The signals below `SIGRTMIN` that the C library keeps for itself are
+    /// not representable.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Realtime(RealtimeSignal),
+}
+
+pub use self::Signal::*;
+
+/// The realtime signal `SIGRTMIN + n`, for an `n` from 0 to
+/// `SIGRTMAX - SIGRTMIN`, as made by `Signal::realtime`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RealtimeSignal(c_int);
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl RealtimeSignal {
+    /// The offset `n` from `SIGRTMIN`.
+    pub fn offset(&self) -> c_int {
+        self.0
+    }
+}
+
+pub const SIGIOT: Signal = SIGABRT;
+
+// The standard signals, in numerical order on Linux
+const SIGNALS: &'static [Signal] = &[
+    SIGHUP,
+    SIGINT,
+    SIGQUIT,
+    SIGILL,
+    SIGTRAP,
+    SIGABRT,
+    SIGBUS,
+    SIGFPE,
+    SIGKILL,
     SIGUSR1,
+    SIGSEGV,
     SIGUSR2,
-};
+    SIGPIPE,
+    SIGALRM,
+    SIGTERM,
+    #[cfg(any(all(target_os = "linux",
+                  any(target_arch = "x86",
+                      target_arch = "x86_64",
+                      target_arch = "aarch64",
+                      target_arch = "arm")),
+              target_os = "android"))]
+    SIGSTKFLT,
+    SIGCHLD,
+    SIGCONT,
+    SIGSTOP,
+    SIGTSTP,
+    SIGTTIN,
+    SIGTTOU,
+    SIGURG,
+    SIGXCPU,
+    SIGXFSZ,
+    SIGVTALRM,
+    SIGPROF,
+    SIGWINCH,
+    SIGIO,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    SIGPWR,
+    SIGSYS,
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    SIGEMT,
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    SIGINFO,
+];
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn sigrtmin() -> c_int {
+    unsafe { ffi::__libc_current_sigrtmin() }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn sigrtmax() -> c_int {
+    unsafe { ffi::__libc_current_sigrtmax() }
+}
+
+impl Signal {
+    /// Returns the signal number.
+    pub fn as_raw(&self) -> c_int {
+        match *self {
+            SIGHUP => libc::SIGHUP,
+            SIGINT => libc::SIGINT,
+            SIGQUIT => libc::SIGQUIT,
+            SIGILL => libc::SIGILL,
+            SIGTRAP => libc::SIGTRAP,
+            SIGABRT => libc::SIGABRT,
+            SIGBUS => libc::SIGBUS,
+            SIGFPE => libc::SIGFPE,
+            SIGKILL => libc::SIGKILL,
+            SIGUSR1 => libc::SIGUSR1,
+            SIGSEGV => libc::SIGSEGV,
+            SIGUSR2 => libc::SIGUSR2,
+            SIGPIPE => libc::SIGPIPE,
+            SIGALRM => libc::SIGALRM,
+            SIGTERM => libc::SIGTERM,
+            #[cfg(any(all(target_os = "linux",
+                          any(target_arch = "x86",
+                              target_arch = "x86_64",
+                              target_arch = "aarch64",
+                              target_arch = "arm")),
+                      target_os = "android"))]
+            SIGSTKFLT => signal::SIGSTKFLT,
+            SIGCHLD => libc::SIGCHLD,
+            SIGCONT => libc::SIGCONT,
+            SIGSTOP => libc::SIGSTOP,
+            SIGTSTP => libc::SIGTSTP,
+            SIGTTIN => libc::SIGTTIN,
+            SIGTTOU => libc::SIGTTOU,
+            SIGURG => libc::SIGURG,
+            SIGXCPU => libc::SIGXCPU,
+            SIGXFSZ => libc::SIGXFSZ,
+            SIGVTALRM => libc::SIGVTALRM,
+            SIGPROF => libc::SIGPROF,
+            SIGWINCH => libc::SIGWINCH,
+            SIGIO => libc::SIGIO,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            SIGPWR => signal::SIGPWR,
+            SIGSYS => libc::SIGSYS,
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            SIGEMT => signal::SIGEMT,
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            SIGINFO => signal::SIGINFO,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Realtime(rt) => sigrtmin() + rt.0,
+        }
+    }
+
+    /// Returns the realtime signal `SIGRTMIN + n`, failing with `EINVAL` if
+    /// that is above `SIGRTMAX`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn realtime(n: c_int) -> Result<Signal> {
+        if n < 0 || n > sigrtmax() - sigrtmin() {
+            return Err(Error::invalid_argument());
+        }
+
+        Ok(Realtime(RealtimeSignal(n)))
+    }
+
+    /// Returns the name of a standard signal, such as `"SIGTERM"`.
+    fn name(&self) -> Option<&'static str> {
+        Some(match *self {
+            SIGHUP => "SIGHUP",
+            SIGINT => "SIGINT",
+            SIGQUIT => "SIGQUIT",
+            SIGILL => "SIGILL",
+            SIGTRAP => "SIGTRAP",
+            SIGABRT => "SIGABRT",
+            SIGBUS => "SIGBUS",
+            SIGFPE => "SIGFPE",
+            SIGKILL => "SIGKILL",
+            SIGUSR1 => "SIGUSR1",
+            SIGSEGV => "SIGSEGV",
+            SIGUSR2 => "SIGUSR2",
+            SIGPIPE => "SIGPIPE",
+            SIGALRM => "SIGALRM",
+            SIGTERM => "SIGTERM",
+            #[cfg(any(all(target_os = "linux",
+                          any(target_arch = "x86",
+                              target_arch = "x86_64",
+                              target_arch = "aarch64",
+                              target_arch = "arm")),
+                      target_os = "android"))]
+            SIGSTKFLT => "SIGSTKFLT",
+            SIGCHLD => "SIGCHLD",
+            SIGCONT => "SIGCONT",
+            SIGSTOP => "SIGSTOP",
+            SIGTSTP => "SIGTSTP",
+            SIGTTIN => "SIGTTIN",
+            SIGTTOU => "SIGTTOU",
+            SIGURG => "SIGURG",
+            SIGXCPU => "SIGXCPU",
+            SIGXFSZ => "SIGXFSZ",
+            SIGVTALRM => "SIGVTALRM",
+            SIGPROF => "SIGPROF",
+            SIGWINCH => "SIGWINCH",
+            SIGIO => "SIGIO",
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            SIGPWR => "SIGPWR",
+            SIGSYS => "SIGSYS",
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            SIGEMT => "SIGEMT",
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            SIGINFO => "SIGINFO",
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Realtime(_) => return None,
+        })
+    }
+
+    /// Iterates over every signal: the standard ones, followed by the
+    /// realtime signals from `SIGRTMIN` to `SIGRTMAX`.
+    pub fn iterator() -> SignalIterator {
+        SignalIterator { next: 0 }
+    }
+}
+
+impl TryFrom<c_int> for Signal {
+    type Error = Error;
+
+    /// Fails with `EINVAL` if `signum` is not a valid signal number.
+    fn try_from(signum: c_int) -> Result<Signal> {
+        if let Some(signal) = SIGNALS.iter().find(|s| s.as_raw() == signum) {
+            return Ok(*signal);
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            if signum >= sigrtmin() && signum <= sigrtmax() {
+                return Ok(Realtime(RealtimeSignal(signum - sigrtmin())));
+            }
+        }
+
+        Err(Error::invalid_argument())
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            None => match *self {
+                Realtime(RealtimeSignal(0)) => f.write_str("SIGRTMIN"),
+                Realtime(RealtimeSignal(n)) => write!(f, "SIGRTMIN+{}", n),
+                _ => unreachable!(),
+            },
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            None => unreachable!(),
+        }
+    }
+}
+
+impl FromStr for Signal {
+    type Err = Error;
 
-// This doesn't always exist, but when it does, it's 7
-pub const SIGEMT: libc::c_int = 7;
+    /// Parses a signal name such as `"SIGTERM"`, or `"SIGRTMIN+n"` for a
+    /// realtime signal.
+    fn from_str(s: &str) -> Result<Signal> {
+        if let Some(signal) = SIGNALS.iter().find(|sig| sig.name() == Some(s)) {
+            return Ok(*signal);
+        }
+
+        if s == "SIGIOT" {
+            return Ok(SIGIOT);
+        }
 
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            if s == "SIGRTMIN" {
+                return Signal::realtime(0);
+            }
+
+            if s.starts_with("SIGRTMIN+") {
+                return match s["SIGRTMIN+".len()..].parse() {
+                    Ok(n) => Signal::realtime(n),
+                    Err(_) => Err(Error::invalid_argument()),
+                };
+            }
+        }
+
+        Err(Error::invalid_argument())
+    }
+}
+
+/// Iterator over all signals, returned by `Signal::iterator`.
+#[derive(Clone, Debug)]
+pub struct SignalIterator {
+    next: usize,
+}
+
+impl Iterator for SignalIterator {
+    type Item = Signal;
+
+    fn next(&mut self) -> Option<Signal> {
+        let i = self.next;
+
+        if i < SIGNALS.len() {
+            self.next += 1;
+            return Some(SIGNALS[i]);
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let n = (i - SIGNALS.len()) as c_int;
+            if n <= sigrtmax() - sigrtmin() {
+                self.next += 1;
+                return Some(Realtime(RealtimeSignal(n)));
+            }
+        }
+
+        None
+    }
+}
+
+/// One more than the largest signal number.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub const NSIG: libc::c_int = 65;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
 pub const NSIG: libc::c_int = 32;
 
+// The size of the union in `siginfo`, which pads the struct to 128 bytes
+#[cfg(all(any(target_os = "linux", target_os = "android"), target_pointer_width = "64"))]
+const SI_FIELDS: usize = 14;
+#[cfg(all(any(target_os = "linux", target_os = "android"), target_pointer_width = "32"))]
+const SI_FIELDS: usize = 29;
+
 pub use self::signal::{
-    SockFlag,
+    SaFlags,
 
     SA_NOCLDSTOP,
     SA_NOCLDWAIT,
@@ -69,8 +397,11 @@ pub use self::signal::sigset_t;
 pub mod signal {
     use libc;
 
+    pub const SIGSTKFLT: libc::c_int = 16;
+    pub const SIGPWR: libc::c_int = 30;
+
     bitflags!(
-        flags SockFlag: libc::c_ulong {
+        flags SaFlags: libc::c_ulong {
             const SA_NOCLDSTOP = 0x00000001,
             const SA_NOCLDWAIT = 0x00000002,
             const SA_NODEFER   = 0x40000000,
@@ -89,26 +420,22 @@ pub mod signal {
         }
     }
 
-    // This definition is not as accurate as it could be, {pid, uid, status} is
-    // actually a giant union. Currently we're only interested in these fields,
-    // however.
     #[repr(C)]
     #[derive(Clone, Copy)]
     pub struct siginfo {
         pub si_signo: libc::c_int,
         pub si_errno: libc::c_int,
         pub si_code: libc::c_int,
-        pub pid: libc::pid_t,
-        pub uid: libc::uid_t,
-        pub status: libc::c_int,
+        // A union of per-signal fields, decoded by `SigInfo::cause`
+        pub si_fields: [usize; super::SI_FIELDS],
     }
 
     #[repr(C)]
-    #[allow(missing_copy_implementations)]
+    #[derive(Clone, Copy)]
     pub struct sigaction {
-        pub sa_handler: extern fn(libc::c_int),
+        pub sa_handler: libc::size_t,
         pub sa_mask: sigset_t,
-        pub sa_flags: SockFlag,
+        pub sa_flags: SaFlags,
         sa_restorer: *mut libc::c_void,
     }
 
@@ -132,8 +459,10 @@ pub mod signal {
 pub mod signal {
     use libc;
 
+    pub const SIGPWR: libc::c_int = 19;
+
     bitflags!(
-        flags SockFlag: libc::c_uint {
+        flags SaFlags: libc::c_uint {
             const SA_NOCLDSTOP = 0x00000001,
             const SA_NOCLDWAIT = 0x00001000,
             const SA_NODEFER   = 0x40000000,
@@ -156,25 +485,27 @@ pub mod signal {
     // actually a giant union. Currently we're only interested in these fields,
     // however.
     #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct siginfo {
         pub si_signo: libc::c_int,
         pub si_code: libc::c_int,
         pub si_errno: libc::c_int,
-        pub pid: libc::pid_t,
-        pub uid: libc::uid_t,
-        pub status: libc::c_int,
+        // A union of per-signal fields, decoded by `SigInfo::cause`
+        pub si_fields: [usize; super::SI_FIELDS],
     }
 
     #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct sigaction {
-        pub sa_flags: SockFlag,
-        pub sa_handler: extern fn(libc::c_int),
+        pub sa_flags: SaFlags,
+        pub sa_handler: libc::size_t,
         pub sa_mask: sigset_t,
         sa_restorer: *mut libc::c_void,
         sa_resv: [libc::c_int; 1],
     }
 
     #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct sigset_t {
         __val: [libc::c_ulong; 32],
     }
@@ -189,8 +520,11 @@ pub mod signal {
 pub mod signal {
     use libc;
 
+    pub const SIGEMT: libc::c_int = 7;
+    pub const SIGINFO: libc::c_int = 29;
+
     bitflags!(
-        flags SockFlag: libc::c_int {
+        flags SaFlags: libc::c_int {
             const SA_NOCLDSTOP = 0x0008,
             const SA_NOCLDWAIT = 0x0020,
             const SA_NODEFER   = 0x0010,
@@ -225,7 +559,8 @@ pub mod signal {
     }
 
     // This structure has more fields, but we're not all that interested in
-    // them.
+    // them. `SigInfo::cause` does not decode it; the fields are reachable
+    // through `SigInfo::as_ref`.
     #[cfg(not(target_os = "dragonfly"))]
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -252,33 +587,35 @@ pub mod signal {
 
     #[cfg(any(target_os = "macos", target_os = "ios"))]
     #[repr(C)]
-    #[allow(missing_copy_implementations)]
+    #[derive(Clone, Copy)]
     pub struct sigaction {
-        pub sa_handler: extern fn(libc::c_int),
+        pub sa_handler: libc::size_t,
         pub sa_mask: sigset_t,
-        pub sa_flags: SockFlag,
+        pub sa_flags: SaFlags,
     }
 
     #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
     #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct sigaction {
-        pub sa_handler: extern fn(libc::c_int),
-        pub sa_flags: SockFlag,
+        pub sa_handler: libc::size_t,
+        pub sa_flags: SaFlags,
         pub sa_mask: sigset_t,
     }
 
     #[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
     #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct sigaction {
-        pub sa_handler: extern fn(libc::c_int),
+        pub sa_handler: libc::size_t,
         pub sa_mask: sigset_t,
-        pub sa_flags: SockFlag,
+        pub sa_flags: SaFlags,
     }
 }
 
 mod ffi {
     use libc::{c_int, pid_t};
-    use super::signal::{sigaction, sigset_t};
+    use super::signal::{sigaction, siginfo, sigset_t};
 
     #[allow(improper_ctypes)]
     extern {
@@ -294,8 +631,23 @@ mod ffi {
 
         pub fn pthread_sigmask(how: c_int, set: *const sigset_t, oldset: *mut sigset_t) -> c_int;
 
+        pub fn sigwait(set: *const sigset_t, sig: *mut c_int) -> c_int;
+        pub fn sigsuspend(mask: *const sigset_t) -> c_int;
+        pub fn sigpending(set: *mut sigset_t) -> c_int;
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        pub fn sigwaitinfo(set: *const sigset_t, info: *mut siginfo) -> c_int;
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        pub fn sigtimedwait(set: *const sigset_t, info: *mut siginfo,
+                            timeout: *const ::sys::time::TimeSpec) -> c_int;
+
         pub fn kill(pid: pid_t, signum: c_int) -> c_int;
         pub fn raise(signum: c_int) -> c_int;
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        pub fn __libc_current_sigrtmin() -> c_int;
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        pub fn __libc_current_sigrtmax() -> c_int;
     }
 }
 
@@ -304,8 +656,6 @@ pub struct SigSet {
     sigset: sigset_t
 }
 
-pub type SigNum = libc::c_int;
-
 impl SigSet {
     pub fn all() -> SigSet {
         let mut sigset: sigset_t = unsafe { mem::uninitialized() };
@@ -321,8 +671,8 @@ impl SigSet {
         SigSet { sigset: sigset }
     }
 
-    pub fn add(&mut self, signum: SigNum) -> Result<()> {
-        let res = unsafe { ffi::sigaddset(&mut self.sigset as *mut sigset_t, signum) };
+    pub fn add(&mut self, signal: Signal) -> Result<()> {
+        let res = unsafe { ffi::sigaddset(&mut self.sigset as *mut sigset_t, signal.as_raw()) };
 
         if res < 0 {
             return Err(Error::Sys(Errno::last()));
@@ -331,8 +681,8 @@ impl SigSet {
         Ok(())
     }
 
-    pub fn remove(&mut self, signum: SigNum) -> Result<()> {
-        let res = unsafe { ffi::sigdelset(&mut self.sigset as *mut sigset_t, signum) };
+    pub fn remove(&mut self, signal: Signal) -> Result<()> {
+        let res = unsafe { ffi::sigdelset(&mut self.sigset as *mut sigset_t, signal.as_raw()) };
 
         if res < 0 {
             return Err(Error::Sys(Errno::last()));
@@ -341,8 +691,8 @@ impl SigSet {
         Ok(())
     }
 
-    pub fn contains(&self, signum: SigNum) -> Result<bool> {
-        let res = unsafe { ffi::sigismember(&self.sigset as *const sigset_t, signum) };
+    pub fn contains(&self, signal: Signal) -> Result<bool> {
+        let res = unsafe { ffi::sigismember(&self.sigset as *const sigset_t, signal.as_raw()) };
 
         match res {
             1 => Ok(true),
@@ -379,6 +729,66 @@ impl SigSet {
         try!(pthread_sigmask(how, Some(self), Some(&mut oldmask)));
         Ok(oldmask)
     }
+
+    /// Gets the signals that are pending for the calling thread or the
+    /// process because they are blocked.
+    pub fn pending() -> Result<SigSet> {
+        let mut set: SigSet = unsafe { mem::uninitialized() };
+        let res = unsafe { ffi::sigpending(&mut set.sigset as *mut sigset_t) };
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(set)
+    }
+
+    /// Waits for one of the signals in the set to become pending, and
+    /// consumes it. The signals must be blocked beforehand.
+    pub fn wait(&self) -> Result<Signal> {
+        let mut signum: c_int = 0;
+        let res = unsafe { ffi::sigwait(&self.sigset as *const sigset_t, &mut signum) };
+
+        match res {
+            0 => Signal::try_from(signum),
+            errno => Err(Error::Sys(Errno::from_i32(errno))),
+        }
+    }
+
+    /// Like `wait`, but also returns the `SigInfo` of the signal.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn wait_info(&self) -> Result<SigInfo> {
+        let mut info: self::signal::siginfo = unsafe { mem::zeroed() };
+        let res = unsafe { ffi::sigwaitinfo(&self.sigset as *const sigset_t, &mut info) };
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(SigInfo::from(info))
+    }
+
+    /// Like `wait_info`, but gives up after `timeout` and returns `None`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn timed_wait(&self, timeout: &TimeSpec) -> Result<Option<SigInfo>> {
+        let mut info: self::signal::siginfo = unsafe { mem::zeroed() };
+        let res = unsafe { ffi::sigtimedwait(&self.sigset as *const sigset_t, &mut info, timeout) };
+
+        if res < 0 {
+            return match Errno::last() {
+                Errno::EAGAIN => Ok(None),
+                errno => Err(Error::Sys(errno)),
+            };
+        }
+
+        Ok(Some(SigInfo::from(info)))
+    }
+
+    /// Replaces the signal mask of the calling thread with this set until a
+    /// signal handler runs or the process is terminated, then restores it.
+    pub fn suspend(&self) {
+        unsafe { ffi::sigsuspend(&self.sigset as *const sigset_t) };
+    }
 }
 
 impl AsRef<sigset_t> for SigSet {
@@ -389,26 +799,114 @@ impl AsRef<sigset_t> for SigSet {
 
 type sigaction_t = self::signal::sigaction;
 
+const SIG_DFL: libc::size_t = 0;
+const SIG_IGN: libc::size_t = 1;
+
+/// What happens when a signal is delivered.
+///
+/// Handlers compare equal when they are at the same address, which is what
+/// `sigaction` reports back.
+#[derive(Clone, Copy, Debug)]
+pub enum SigHandler {
+    /// The default action for the signal (`SIG_DFL`), e.g. terminating.
+    Default,
+    /// Discard the signal (`SIG_IGN`).
+    Ignore,
+    /// Call a handler with the signal number.
+    Handler(extern fn(libc::c_int)),
+    /// Call a handler with the signal number, a `SigInfo` describing why the
+    /// signal was sent, and the interrupted `ucontext_t`. Implies `SA_SIGINFO`.
+    SigAction(extern fn(libc::c_int, *mut SigInfo, *mut libc::c_void)),
+}
+
+impl SigHandler {
+    // The `sa_handler` value, and whether it takes a `SigInfo`. Function
+    // pointers themselves are not reliably comparable.
+    fn key(&self) -> (libc::size_t, bool) {
+        match *self {
+            SigHandler::Default => (SIG_DFL, false),
+            SigHandler::Ignore => (SIG_IGN, false),
+            SigHandler::Handler(f) => (f as libc::size_t, false),
+            SigHandler::SigAction(f) => (f as libc::size_t, true),
+        }
+    }
+}
+
+impl PartialEq for SigHandler {
+    fn eq(&self, other: &SigHandler) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SigHandler {}
+
+impl Hash for SigHandler {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+/// The disposition of a signal, as installed and returned by `sigaction`.
+#[derive(Clone, Copy)]
 pub struct SigAction {
     sigaction: sigaction_t
 }
 
 impl SigAction {
-    pub fn new(handler: extern fn(libc::c_int), flags: SockFlag, mask: SigSet) -> SigAction {
-        let mut s = unsafe { mem::uninitialized::<sigaction_t>() };
-        s.sa_handler = handler;
-        s.sa_flags = flags;
+    /// Creates an action that runs `handler` with `mask` added to the blocked
+    /// signals. `SA_SIGINFO` is set or cleared to match the kind of handler.
+    pub fn new(handler: SigHandler, flags: SaFlags, mask: SigSet) -> SigAction {
+        let mut s = unsafe { mem::zeroed::<sigaction_t>() };
+        s.sa_handler = handler.key().0;
+        s.sa_flags = match handler {
+            SigHandler::SigAction(_) => flags | SA_SIGINFO,
+            _ => flags - SA_SIGINFO,
+        };
         s.sa_mask = mask.sigset;
 
         SigAction { sigaction: s }
     }
+
+    pub fn handler(&self) -> SigHandler {
+        match self.sigaction.sa_handler {
+            SIG_DFL => SigHandler::Default,
+            SIG_IGN => SigHandler::Ignore,
+            f if self.flags().contains(SA_SIGINFO) => SigHandler::SigAction(unsafe { mem::transmute(f) }),
+            f => SigHandler::Handler(unsafe { mem::transmute(f) }),
+        }
+    }
+
+    pub fn flags(&self) -> SaFlags {
+        // Only the low bits are meaningful where the C field is narrower
+        SaFlags::from_bits_truncate(self.sigaction.sa_flags.bits())
+    }
+
+    /// The signals blocked while the handler runs, in addition to the signal
+    /// itself unless `SA_NODEFER` is set.
+    pub fn mask(&self) -> SigSet {
+        SigSet { sigset: self.sigaction.sa_mask }
+    }
+}
+
+impl fmt::Debug for SigAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SigAction")
+            .field("handler", &self.handler())
+            .field("flags", &self.flags())
+            .finish()
+    }
 }
 
-pub unsafe fn sigaction(signum: SigNum, sigaction: &SigAction) -> Result<SigAction> {
+/// Changes the action taken when `signal` is delivered, and returns the
+/// previous action.
+///
+/// This is unsafe because the handler runs asynchronously, interrupting
+/// arbitrary code, and must only call async-signal-safe functions.
+pub unsafe fn sigaction(signal: Signal, sigaction: &SigAction) -> Result<SigAction> {
     let mut oldact = mem::uninitialized::<sigaction_t>();
 
     let res =
-        ffi::sigaction(signum, &sigaction.sigaction as *const sigaction_t, &mut oldact as *mut sigaction_t);
+        ffi::sigaction(signal.as_raw(), &sigaction.sigaction as *const sigaction_t, &mut oldact as *mut sigaction_t);
 
     if res < 0 {
         return Err(Error::Sys(Errno::last()));
@@ -455,7 +953,13 @@ pub fn pthread_sigmask(how: HowFlag,
     Ok(())
 }
 
-pub fn kill(pid: libc::pid_t, signum: SigNum) -> Result<()> {
+/// Sends `signal` to the process or process group `pid`. With `None`, only
+/// checks that the process exists and may be signalled.
+pub fn kill<T: Into<Option<Signal>>>(pid: libc::pid_t, signal: T) -> Result<()> {
+    let signum = match signal.into() {
+        Some(signal) => signal.as_raw(),
+        None => 0,
+    };
     let res = unsafe { ffi::kill(pid, signum) };
 
     if res < 0 {
@@ -465,8 +969,8 @@ pub fn kill(pid: libc::pid_t, signum: SigNum) -> Result<()> {
     Ok(())
 }
 
-pub fn raise(signum: SigNum) -> Result<()> {
-    let res = unsafe { ffi::raise(signum) };
+pub fn raise(signal: Signal) -> Result<()> {
+    let res = unsafe { ffi::raise(signal.as_raw()) };
 
     if res < 0 {
         return Err(Error::Sys(Errno::last()));
@@ -475,6 +979,371 @@ pub fn raise(signum: SigNum) -> Result<()> {
     Ok(())
 }
 
+/// Information about a delivered signal, as passed to `SA_SIGINFO` handlers
+/// and returned by `sigwaitinfo`, `waitid` and `signalfd`.
+///
+/// Only Linux and Android decode the per-signal fields, with `cause`. On other
+/// systems the raw `siginfo` is available through `as_ref`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct SigInfo {
+    info: self::signal::siginfo,
+}
+
+impl SigInfo {
+    /// The signal that was delivered.
+    pub fn signal(&self) -> Result<Signal> {
+        Signal::try_from(self.info.si_signo)
+    }
+
+    /// An error number associated with the signal, usually 0.
+    pub fn errno(&self) -> c_int {
+        self.info.si_errno
+    }
+
+    /// The raw `si_code`, which `cause` decodes.
+    pub fn code(&self) -> c_int {
+        self.info.si_code
+    }
+}
+
+impl From<self::signal::siginfo> for SigInfo {
+    fn from(info: self::signal::siginfo) -> SigInfo {
+        SigInfo { info: info }
+    }
+}
+
+impl AsRef<self::signal::siginfo> for SigInfo {
+    fn as_ref(&self) -> &self::signal::siginfo {
+        &self.info
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use self::cause::*;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod cause {
+    use libc::{self, c_int, pid_t, uid_t};
+    use std::convert::TryFrom;
+    use std::{fmt, ptr};
+    use sys::wait::WaitStatus;
+    use super::{SigInfo, Signal};
+
+    const SI_USER: c_int = 0;
+    const SI_QUEUE: c_int = -1;
+    const SI_TIMER: c_int = -2;
+    const SI_MESGQ: c_int = -3;
+    const SI_TKILL: c_int = -6;
+
+    const CLD_EXITED: c_int = 1;
+    const CLD_KILLED: c_int = 2;
+    const CLD_DUMPED: c_int = 3;
+    const CLD_TRAPPED: c_int = 4;
+    const CLD_STOPPED: c_int = 5;
+    const CLD_CONTINUED: c_int = 6;
+
+    const SEGV_MAPERR: c_int = 1;
+    const SEGV_ACCERR: c_int = 2;
+    const BUS_ADRALN: c_int = 1;
+    const BUS_ADRERR: c_int = 2;
+    const BUS_OBJERR: c_int = 3;
+
+    // Offsets into the union of per-signal fields
+    const PID: usize = 0;
+    const UID: usize = 4;
+    const TIMERID: usize = 0;
+    const OVERRUN: usize = 4;
+    const VALUE: usize = 8;
+    const STATUS: usize = 8;
+    const ADDR: usize = 0;
+
+    /// The value sent along with a queued signal or by a timer. It is a union
+    /// of an integer and a pointer, and the sender decides which is meant.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct SigVal(usize);
+
+    impl SigVal {
+        pub fn sival_int(&self) -> c_int {
+            unsafe { *(&self.0 as *const usize as *const c_int) }
+        }
+
+        pub fn sival_ptr(&self) -> *mut libc::c_void {
+            self.0 as *mut libc::c_void
+        }
+    }
+
+    impl fmt::Debug for SigVal {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "SigVal({:#x})", self.0)
+        }
+    }
+
+    /// Why a memory access faulted, for `SigCause::Fault`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum FaultCode {
+        /// `SIGSEGV`: the address is not mapped.
+        SegvMapErr,
+        /// `SIGSEGV`: the mapping does not permit the access.
+        SegvAccErr,
+        /// `SIGBUS`: the address is misaligned.
+        BusAdrAln,
+        /// `SIGBUS`: the physical address does not exist.
+        BusAdrErr,
+        /// `SIGBUS`: a hardware error specific to the object, e.g. a
+        /// truncated file mapping.
+        BusObjErr,
+        /// Another `si_code`.
+        Other(c_int),
+    }
+
+    /// Why a signal was sent, decoded from a `SigInfo`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum SigCause {
+        /// Sent by `kill`, `raise` or `tgkill`.
+        Kill { pid: pid_t, uid: uid_t },
+        /// Sent by `sigqueue` or a message queue notification, with a value.
+        Queued { pid: pid_t, uid: uid_t, value: SigVal },
+        /// A POSIX timer expired. `overrun` counts the further expirations
+        /// that were merged into this signal.
+        Timer { overrun: c_int, value: SigVal },
+        /// A child process changed state (`SIGCHLD`).
+        Child { uid: uid_t, status: WaitStatus },
+        /// A memory access faulted at `addr` (`SIGSEGV` or `SIGBUS`).
+        Fault { addr: *mut libc::c_void, code: FaultCode },
+        /// Any other cause, described by the raw `si_code`.
+        Other(c_int),
+    }
+
+    impl SigInfo {
+        fn field<T: Copy>(&self, offset: usize) -> T {
+            let fields = self.info.si_fields.as_ptr() as *const u8;
+            unsafe { ptr::read(fields.offset(offset as isize) as *const T) }
+        }
+
+        fn set_field<T: Copy>(&mut self, offset: usize, value: T) {
+            let fields = self.info.si_fields.as_mut_ptr() as *mut u8;
+            unsafe { ptr::write(fields.offset(offset as isize) as *mut T, value) }
+        }
+
+        fn child_status(&self) -> Option<WaitStatus> {
+            let pid = self.field(PID);
+            let status: c_int = self.field(STATUS);
+
+            match self.info.si_code {
+                CLD_EXITED => Some(WaitStatus::Exited(pid, status as i8)),
+                CLD_CONTINUED => Some(WaitStatus::Continued(pid)),
+                // PTRACE_O_TRACESYSGOOD sets the high bit of the stop signal
+                CLD_TRAPPED if status == libc::SIGTRAP | 0x80 => Some(WaitStatus::PtraceSyscall(pid)),
+                code => {
+                    // Otherwise the status is the signal that caused the change,
+                    // with any ptrace event above it. A signal that `Signal`
+                    // cannot represent is passed on raw, encoded as `waitpid`
+                    // would, so that the change is not lost.
+                    let signal = Signal::try_from(status & 0xff);
+
+                    match (code, signal) {
+                        (CLD_KILLED, Ok(signal)) => Some(WaitStatus::Signaled(pid, signal, false)),
+                        (CLD_DUMPED, Ok(signal)) => Some(WaitStatus::Signaled(pid, signal, true)),
+                        (CLD_TRAPPED, Ok(signal)) if status >> 8 != 0 => {
+                            Some(WaitStatus::PtraceEvent(pid, signal, status >> 8))
+                        }
+                        (CLD_TRAPPED, Ok(signal)) | (CLD_STOPPED, Ok(signal)) => Some(WaitStatus::Stopped(pid, signal)),
+                        (CLD_KILLED, Err(_)) => Some(WaitStatus::Unknown(pid, status & 0x7f)),
+                        (CLD_DUMPED, Err(_)) => Some(WaitStatus::Unknown(pid, status & 0x7f | 0x80)),
+                        (CLD_TRAPPED, Err(_)) | (CLD_STOPPED, Err(_)) => {
+                            Some(WaitStatus::Unknown(pid, status << 8 | 0x7f))
+                        }
+                        _ => None,
+                    }
+                }
+            }
+        }
+
+        /// Decodes the fields that the kernel filled in for this signal and
+        /// its `si_code`.
+        pub fn cause(&self) -> SigCause {
+            let code = self.info.si_code;
+            let signum = self.info.si_signo;
+
+            match code {
+                SI_USER | SI_TKILL => {
+                    return SigCause::Kill { pid: self.field(PID), uid: self.field(UID) };
+                }
+                SI_QUEUE | SI_MESGQ => {
+                    return SigCause::Queued {
+                        pid: self.field(PID),
+                        uid: self.field(UID),
+                        value: SigVal(self.field(VALUE)),
+                    };
+                }
+                SI_TIMER => {
+                    return SigCause::Timer { overrun: self.field(OVERRUN), value: SigVal(self.field(VALUE)) };
+                }
+                _ => {}
+            }
+
+            // The remaining causes are only reported by the kernel itself
+            if code <= 0 {
+                return SigCause::Other(code);
+            }
+
+            if signum == libc::SIGCHLD {
+                if let Some(status) = self.child_status() {
+                    return SigCause::Child { uid: self.field(UID), status: status };
+                }
+            } else if signum == libc::SIGSEGV {
+                let code = match code {
+                    SEGV_MAPERR => FaultCode::SegvMapErr,
+                    SEGV_ACCERR => FaultCode::SegvAccErr,
+                    code => FaultCode::Other(code),
+                };
+                return SigCause::Fault { addr: self.field(ADDR), code: code };
+            } else if signum == libc::SIGBUS {
+                let code = match code {
+                    BUS_ADRALN => FaultCode::BusAdrAln,
+                    BUS_ADRERR => FaultCode::BusAdrErr,
+                    BUS_OBJERR => FaultCode::BusObjErr,
+                    code => FaultCode::Other(code),
+                };
+                return SigCause::Fault { addr: self.field(ADDR), code: code };
+            }
+
+            SigCause::Other(code)
+        }
+    }
+
+    impl fmt::Debug for SigInfo {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_struct("SigInfo")
+                .field("signal", &self.signal())
+                .field("errno", &self.errno())
+                .field("cause", &self.cause())
+                .finish()
+        }
+    }
+
+    #[cfg(feature = "signalfd")]
+    impl From<::sys::signalfd::siginfo> for SigInfo {
+        fn from(ssi: ::sys::signalfd::siginfo) -> SigInfo {
+            // Copied out of the packed struct first
+            let signum = ssi.ssi_signo as c_int;
+            let code = ssi.ssi_code;
+
+            let mut info: SigInfo = unsafe { ::std::mem::zeroed() };
+            info.info.si_signo = signum;
+            info.info.si_errno = ssi.ssi_errno;
+            info.info.si_code = code;
+
+            // Put each field where `cause` expects it for this kind of signal
+            if code == SI_TIMER {
+                info.set_field(TIMERID, ssi.ssi_tid as c_int);
+                info.set_field(OVERRUN, ssi.ssi_overrun as c_int);
+                info.set_field(VALUE, ssi.ssi_ptr as usize);
+            } else if code > 0 && (signum == libc::SIGSEGV || signum == libc::SIGBUS) {
+                info.set_field(ADDR, ssi.ssi_addr as usize);
+            } else {
+                info.set_field(PID, ssi.ssi_pid as pid_t);
+                info.set_field(UID, ssi.ssi_uid as uid_t);
+                if code == SI_QUEUE || code == SI_MESGQ {
+                    info.set_field(VALUE, ssi.ssi_ptr as usize);
+                } else if signum == libc::SIGCHLD {
+                    info.set_field(STATUS, ssi.ssi_status as c_int);
+                }
+            }
+
+            info
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use self::sigev::*;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sigev {
+    use libc::{self, c_int, pid_t};
+    use std::mem;
+    use super::Signal;
+
+    pub const SIGEV_SIGNAL: c_int = 0;
+    pub const SIGEV_NONE: c_int = 1;
+    pub const SIGEV_THREAD_ID: c_int = 4;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct sigevent {
+        // Actually a union of an int and a pointer
+        pub sigev_value: *mut libc::c_void,
+        pub sigev_signo: c_int,
+        pub sigev_notify: c_int,
+        // Actually a union, of which only the thread ID is of interest
+        pub sigev_notify_thread_id: c_int,
+        #[cfg(target_pointer_width = "64")]
+        __pad: [c_int; 11],
+        #[cfg(target_pointer_width = "32")]
+        __pad: [c_int; 12],
+    }
+
+    /// How an event such as a timer expiration is reported, for
+    /// `timer_create`, `mq_notify` and POSIX AIO.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nix::sys::signal::{SigEvent, SIGUSR1};
+    ///
+    /// // Deliver SIGUSR1 to the process, with 42 in `si_value`
+    /// let sev = SigEvent::signal(SIGUSR1).value(42);
+    /// ```
+    #[derive(Clone, Copy, Debug)]
+    pub struct SigEvent {
+        sigevent: sigevent,
+    }
+
+    impl SigEvent {
+        fn with_notify(notify: c_int, signum: c_int) -> SigEvent {
+            let mut sev: sigevent = unsafe { mem::zeroed() };
+            sev.sigev_notify = notify;
+            sev.sigev_signo = signum;
+
+            SigEvent { sigevent: sev }
+        }
+
+        /// No notification; the event can only be polled, e.g. with
+        /// `timer_gettime`.
+        pub fn none() -> SigEvent {
+            SigEvent::with_notify(SIGEV_NONE, 0)
+        }
+
+        /// Queues `signal` for the process.
+        pub fn signal(signal: Signal) -> SigEvent {
+            SigEvent::with_notify(SIGEV_SIGNAL, signal.as_raw())
+        }
+
+        /// Queues `signal` for the thread `tid` of the calling process, as
+        /// returned by `unistd::gettid`.
+        pub fn thread_id(signal: Signal, tid: pid_t) -> SigEvent {
+            let mut sev = SigEvent::with_notify(SIGEV_THREAD_ID, signal.as_raw());
+            sev.sigevent.sigev_notify_thread_id = tid;
+            sev
+        }
+
+        /// Sets the value passed along with the signal, available as
+        /// `si_value` in the `siginfo` or `ssi_ptr` from a `signalfd`.
+        pub fn value(mut self, sival: isize) -> SigEvent {
+            self.sigevent.sigev_value = sival as *mut libc::c_void;
+            self
+        }
+    }
+
+    impl AsRef<sigevent> for SigEvent {
+        fn as_ref(&self) -> &sigevent {
+            &self.sigevent
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -516,4 +1385,92 @@ mod tests {
         assert!(oldmask.contains(SIGUSR1).unwrap());
         assert!(!oldmask.contains(SIGUSR2).unwrap());
     }
+
+    #[test]
+    fn test_signal_conversions() {
+        use std::convert::TryFrom;
+
+        assert_eq!(Signal::try_from(libc::SIGTERM), Ok(SIGTERM));
+        assert_eq!(SIGTERM.as_raw(), libc::SIGTERM);
+        assert!(Signal::try_from(0).is_err());
+
+        assert_eq!(SIGTERM.to_string(), "SIGTERM");
+        assert_eq!("SIGTERM".parse::<Signal>(), Ok(SIGTERM));
+        assert_eq!("SIGIOT".parse::<Signal>(), Ok(SIGABRT));
+        assert!("SIGFOO".parse::<Signal>().is_err());
+
+        for signal in Signal::iterator() {
+            assert_eq!(Signal::try_from(signal.as_raw()), Ok(signal));
+            assert_eq!(signal.to_string().parse::<Signal>(), Ok(signal));
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn test_realtime_signals() {
+        use std::convert::TryFrom;
+
+        let rtmin = Signal::realtime(0).unwrap();
+        assert_eq!(rtmin.to_string(), "SIGRTMIN");
+        assert_eq!(Signal::realtime(2).unwrap().to_string(), "SIGRTMIN+2");
+        assert_eq!("SIGRTMIN+2".parse::<Signal>(), Signal::realtime(2));
+        assert_eq!(Signal::try_from(rtmin.as_raw() + 2), Signal::realtime(2));
+        assert!(Signal::realtime(-1).is_err());
+        assert!(Signal::realtime(1000).is_err());
+        match Signal::realtime(2).unwrap() {
+            Realtime(rt) => assert_eq!(rt.offset(), 2),
+            signal => panic!("unexpected signal {}", signal),
+        }
+
+        let last = Signal::iterator().last().unwrap();
+        assert!(last.as_raw() > rtmin.as_raw());
+        assert!(last.as_raw() < NSIG);
+        assert!(Signal::try_from(last.as_raw() + 1).is_err());
+
+        // Kept by the C library, below SIGRTMIN
+        for signum in 32..rtmin.as_raw() {
+            assert!(Signal::try_from(signum).is_err());
+        }
+    }
+
+    #[test]
+    fn test_sigaction() {
+        use std::sync::atomic::{AtomicBool, ATOMIC_BOOL_INIT, Ordering};
+
+        static HANDLED: AtomicBool = ATOMIC_BOOL_INIT;
+
+        extern fn handler(signum: libc::c_int, info: *mut SigInfo, _: *mut libc::c_void) {
+            let info = unsafe { &*info };
+            HANDLED.store(signum == libc::SIGWINCH && info.signal() == Ok(SIGWINCH), Ordering::SeqCst);
+        }
+
+        // SIGWINCH is ignored by default, so a stray one cannot hurt
+        let mut mask = SigSet::empty();
+        mask.add(SIGUSR2).unwrap();
+        let action = SigAction::new(SigHandler::SigAction(handler), SA_RESTART, mask);
+        assert!(action.flags().contains(SA_SIGINFO));
+
+        let old = unsafe { sigaction(SIGWINCH, &action) }.unwrap();
+        assert_eq!(old.handler(), SigHandler::Default);
+
+        raise(SIGWINCH).unwrap();
+        assert!(HANDLED.load(Ordering::SeqCst));
+
+        let ignore = SigAction::new(SigHandler::Ignore, SA_SIGINFO, SigSet::empty());
+        assert!(!ignore.flags().contains(SA_SIGINFO));
+
+        let installed = unsafe { sigaction(SIGWINCH, &ignore) }.unwrap();
+        assert_eq!(installed.handler(), SigHandler::SigAction(handler));
+        assert!(installed.flags().contains(SA_RESTART | SA_SIGINFO));
+        assert!(installed.mask().contains(SIGUSR2).unwrap());
+
+        let installed = unsafe { sigaction(SIGWINCH, &old) }.unwrap();
+        assert_eq!(installed.handler(), SigHandler::Ignore);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn test_siginfo_size() {
+        assert_eq!(mem::size_of::<SigInfo>(), 128);
+    }
 }
@@ -15,14 +15,17 @@
 //!
 //! Please note that signal discarding is not specific to `signalfd`, but also happens with regular
 //! signal handlers.
-use libc::{c_int, pid_t, uid_t};
+use libc::c_int;
 use {Error, Result};
+use fd::Fd;
 use unistd;
 use errno::Errno;
+pub use sys::signal::{self, SigSet, Signal};
+use sys::signal::SigInfo;
 use sys::signal::signal::siginfo as signal_siginfo;
-pub use sys::signal::{self, SigSet};
 
-use std::os::unix::io::{RawFd, AsRawFd};
+use std::convert::TryFrom;
+use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd, FromRawFd};
 use std::mem;
 
 mod ffi {
@@ -94,7 +97,7 @@ pub fn signalfd(fd: RawFd, mask: &SigSet, flags: SfdFlags) -> Result<RawFd> {
 /// }
 /// ```
 #[derive(Debug)]
-pub struct SignalFd(RawFd);
+pub struct SignalFd(Fd);
 
 impl SignalFd {
     pub fn new(mask: &SigSet) -> Result<SignalFd> {
@@ -104,17 +107,17 @@ impl SignalFd {
     pub fn with_flags(mask: &SigSet, flags: SfdFlags) -> Result<SignalFd> {
         let fd = try!(signalfd(CREATE_NEW_FD, mask, flags));
 
-        Ok(SignalFd(fd))
+        Ok(SignalFd(unsafe { Fd::from_raw_fd(fd) }))
     }
 
     pub fn set_mask(&mut self, mask: &SigSet) -> Result<()> {
-        signalfd(self.0, mask, SfdFlags::empty()).map(|_| ())
+        signalfd(self.0.as_raw_fd(), mask, SfdFlags::empty()).map(|_| ())
     }
 
     pub fn read_signal(&mut self) -> Result<Option<siginfo>> {
         let mut buffer: [u8; SIGINFO_SIZE] = unsafe { mem::uninitialized() };
 
-        match unistd::read(self.0, &mut buffer) {
+        match unistd::read(self.0.as_raw_fd(), &mut buffer) {
             Ok(SIGINFO_SIZE) => Ok(Some(unsafe { mem::transmute_copy(&buffer) })),
             Ok(_) => unreachable!("partial read on signalfd"),
             Err(Error::Sys(Errno::EAGAIN)) => Ok(None),
@@ -123,15 +126,15 @@ impl SignalFd {
     }
 }
 
-impl Drop for SignalFd {
-    fn drop(&mut self) {
-        let _ = unistd::close(self.0);
+impl AsRawFd for SignalFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
     }
 }
 
-impl AsRawFd for SignalFd {
-    fn as_raw_fd(&self) -> RawFd {
-        self.0
+impl IntoRawFd for SignalFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
     }
 }
 
@@ -171,16 +174,18 @@ pub struct siginfo {
     pub ssi_addr: u64,
 }
 
+impl siginfo {
+    /// The signal that was read.
+    pub fn signal(&self) -> Result<Signal> {
+        Signal::try_from(self.ssi_signo as c_int)
+    }
+}
+
+/// Deprecated: kept for compatibility, use `SigInfo::from` instead, which
+/// decodes the fields with `SigInfo::cause`.
 impl Into<signal_siginfo> for siginfo {
     fn into(self) -> signal_siginfo {
-        signal_siginfo {
-            si_signo: self.ssi_signo as c_int,
-            si_errno: self.ssi_errno as c_int,
-            si_code: self.ssi_code as c_int,
-            pid: self.ssi_pid as pid_t,
-            uid: self.ssi_uid as uid_t,
-            status: self.ssi_status as c_int,
-        }
+        *SigInfo::from(self).as_ref()
     }
 }
 
@@ -194,6 +199,23 @@ mod tests {
         assert_eq!(mem::size_of::<siginfo>() + SIGINFO_PADDING, SIGINFO_SIZE);
     }
 
+    #[test]
+    fn convert_siginfo() {
+        use sys::signal::{SigCause, SIGUSR1};
+
+        let mut ssi: siginfo = unsafe { mem::zeroed() };
+        ssi.ssi_signo = SIGUSR1.as_raw() as u32;
+        ssi.ssi_pid = 42;
+
+        let info: signal_siginfo = ssi.clone().into();
+        assert_eq!(info.si_signo, SIGUSR1.as_raw());
+        assert_eq!(SigInfo::from(info).cause(), SigInfo::from(ssi).cause());
+        match SigInfo::from(info).cause() {
+            SigCause::Kill { pid, .. } => assert_eq!(pid, 42),
+            cause => panic!("unexpected cause {:?}", cause),
+        }
+    }
+
     #[test]
     fn create_signalfd() {
         let mask = SigSet::empty();
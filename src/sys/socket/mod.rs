@@ -4,11 +4,12 @@
 use {Error, Result, from_ffi};
 use errno::Errno;
 use features;
+use fd::Fd;
 use fcntl::{fcntl, FD_CLOEXEC, O_NONBLOCK};
 use fcntl::FcntlArg::{F_SETFD, F_SETFL};
 use libc::{c_void, c_int, socklen_t, size_t, pid_t, uid_t, gid_t};
 use std::{mem, ptr, slice};
-use std::os::unix::io::RawFd;
+use std::os::unix::io::{RawFd, FromRawFd};
 use sys::uio::IoVec;
 
 mod addr;
@@ -366,6 +367,11 @@ pub fn socket(domain: AddressFamily, ty: SockType, flags: SockFlag, protocol: c_
     Ok(res)
 }
 
+/// Like `socket`, but returns an owned `Fd` that is closed on drop.
+pub fn socket_owned(domain: AddressFamily, ty: SockType, flags: SockFlag, protocol: c_int) -> Result<Fd> {
+    socket(domain, ty, flags, protocol).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 /// Create a pair of connected sockets
 ///
 /// [Further reading](http://man7.org/linux/man-pages/man2/socketpair.2.html)
@@ -400,6 +406,14 @@ pub fn socketpair(domain: AddressFamily, ty: SockType, protocol: c_int,
     Ok((fds[0], fds[1]))
 }
 
+/// Like `socketpair`, but returns owned `Fd`s that are closed on drop.
+pub fn socketpair_owned(domain: AddressFamily, ty: SockType, protocol: c_int,
+                        flags: SockFlag) -> Result<(Fd, Fd)> {
+    let (fd1, fd2) = try!(socketpair(domain, ty, protocol, flags));
+
+    unsafe { Ok((Fd::from_raw_fd(fd1), Fd::from_raw_fd(fd2))) }
+}
+
 /// Listen for connections on a socket
 ///
 /// [Further reading](http://man7.org/linux/man-pages/man2/listen.2.html)
@@ -433,6 +447,11 @@ pub fn accept(sockfd: RawFd) -> Result<RawFd> {
     Ok(res)
 }
 
+/// Like `accept`, but returns an owned `Fd` that is closed on drop.
+pub fn accept_owned(sockfd: RawFd) -> Result<Fd> {
+    accept(sockfd).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 /// Accept a connection on a socket
 ///
 /// [Further reading](http://man7.org/linux/man-pages/man2/accept.2.html)
@@ -440,6 +459,11 @@ pub fn accept4(sockfd: RawFd, flags: SockFlag) -> Result<RawFd> {
     accept4_polyfill(sockfd, flags)
 }
 
+/// Like `accept4`, but returns an owned `Fd` that is closed on drop.
+pub fn accept4_owned(sockfd: RawFd, flags: SockFlag) -> Result<Fd> {
+    accept4(sockfd, flags).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 #[inline]
 fn accept4_polyfill(sockfd: RawFd, flags: SockFlag) -> Result<RawFd> {
     let res =  unsafe { ffi::accept(sockfd, ptr::null_mut(), ptr::null_mut()) };
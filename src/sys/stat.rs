@@ -3,23 +3,80 @@ pub use libc::stat as FileStat;
 
 use {Error, Result, NixPath, from_ffi};
 use errno::Errno;
-use libc::mode_t;
-use std::mem;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use fcntl::{AtFlags, at_rawfd};
+use libc::{self, c_long, mode_t};
+use sys::time::TimeSpec;
+use std::{fmt, mem};
 use std::os::unix::io::RawFd;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 mod ffi {
     use libc::{c_char, c_int, mode_t, dev_t};
-    pub use libc::{stat, fstat, lstat};
+    pub use libc::{stat, fstat, lstat, chmod, fchmod, mkdir, mkfifo};
 
     extern {
         pub fn mknod(pathname: *const c_char, mode: mode_t, dev: dev_t) -> c_int;
         pub fn umask(mask: mode_t) -> mode_t;
     }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    extern {
+        pub fn fstatat(dirfd: c_int, pathname: *const c_char, buf: *mut stat, flags: c_int) -> c_int;
+        pub fn mkdirat(dirfd: c_int, pathname: *const c_char, mode: mode_t) -> c_int;
+        pub fn mknodat(dirfd: c_int, pathname: *const c_char, mode: mode_t, dev: dev_t) -> c_int;
+        pub fn fchmodat(dirfd: c_int, pathname: *const c_char, mode: mode_t, flags: c_int) -> c_int;
+        pub fn utimensat(dirfd: c_int, pathname: *const c_char, times: *const ::sys::time::TimeSpec, flags: c_int) -> c_int;
+        pub fn futimens(fd: c_int, times: *const ::sys::time::TimeSpec) -> c_int;
+    }
+
+    #[cfg(target_os = "linux")]
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct statx_timestamp {
+        pub tv_sec: i64,
+        pub tv_nsec: u32,
+        pub __reserved: i32,
+    }
+
+    // doc: http://man7.org/linux/man-pages/man2/statx.2.html
+    #[cfg(target_os = "linux")]
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct statx {
+        pub stx_mask: u32,
+        pub stx_blksize: u32,
+        pub stx_attributes: u64,
+        pub stx_nlink: u32,
+        pub stx_uid: u32,
+        pub stx_gid: u32,
+        pub stx_mode: u16,
+        pub __spare0: u16,
+        pub stx_ino: u64,
+        pub stx_size: u64,
+        pub stx_blocks: u64,
+        pub stx_attributes_mask: u64,
+        pub stx_atime: statx_timestamp,
+        pub stx_btime: statx_timestamp,
+        pub stx_ctime: statx_timestamp,
+        pub stx_mtime: statx_timestamp,
+        pub stx_rdev_major: u32,
+        pub stx_rdev_minor: u32,
+        pub stx_dev_major: u32,
+        pub stx_dev_minor: u32,
+        pub stx_mnt_id: u64,
+        pub stx_dio_mem_align: u32,
+        pub stx_dio_offset_align: u32,
+        pub __spare3: [u64; 12],
+    }
 }
 
 bitflags!(
     flags SFlag: mode_t {
+        const S_IFMT   = 0o170000,
         const S_IFREG  = 0o100000,
+        const S_IFDIR  = 0o040000,
+        const S_IFLNK  = 0o120000,
         const S_IFCHR  = 0o020000,
         const S_IFBLK  = 0o060000,
         const S_IFIFO  = 0o010000,
@@ -27,6 +84,57 @@ bitflags!(
     }
 );
 
+/// The type of a file, as encoded in the `S_IFMT` bits of its mode.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FileType {
+    Fifo,
+    CharacterDevice,
+    Directory,
+    BlockDevice,
+    File,
+    Symlink,
+    Socket,
+}
+
+impl FileType {
+    /// Extracts the file type from a raw `st_mode`, or `None` for a type this
+    /// crate does not know about.
+    pub fn from_mode(mode: mode_t) -> Option<FileType> {
+        let kind = SFlag::from_bits_truncate(mode) & S_IFMT;
+
+        if kind == S_IFIFO {
+            Some(FileType::Fifo)
+        } else if kind == S_IFCHR {
+            Some(FileType::CharacterDevice)
+        } else if kind == S_IFDIR {
+            Some(FileType::Directory)
+        } else if kind == S_IFBLK {
+            Some(FileType::BlockDevice)
+        } else if kind == S_IFREG {
+            Some(FileType::File)
+        } else if kind == S_IFLNK {
+            Some(FileType::Symlink)
+        } else if kind == S_IFSOCK {
+            Some(FileType::Socket)
+        } else {
+            None
+        }
+    }
+
+    /// The `S_IFMT` bits corresponding to this type, as used by `mknod`.
+    pub fn to_sflag(&self) -> SFlag {
+        match *self {
+            FileType::Fifo => S_IFIFO,
+            FileType::CharacterDevice => S_IFCHR,
+            FileType::Directory => S_IFDIR,
+            FileType::BlockDevice => S_IFBLK,
+            FileType::File => S_IFREG,
+            FileType::Symlink => S_IFLNK,
+            FileType::Socket => S_IFSOCK,
+        }
+    }
+}
+
 bitflags! {
     flags Mode: mode_t {
         const S_IRWXU = 0o0700,
@@ -59,12 +167,28 @@ pub fn mknod<P: ?Sized + NixPath>(path: &P, kind: SFlag, perm: Mode, dev: dev_t)
     from_ffi(res)
 }
 
+/// Combines a major and minor device number into a `dev_t`, as expected by
+/// `mknod` and reported by `st_rdev`.
 #[cfg(target_os = "linux")]
-const MINORBITS: usize = 20;
+pub fn mkdev(major: u64, minor: u64) -> dev_t {
+    ((major & 0xfffff000) << 32) |
+    ((major & 0x00000fff) << 8) |
+    ((minor & 0xffffff00) << 12) |
+    (minor & 0x000000ff)
+}
 
+/// Extracts the major number from a device number created by `mkdev`.
 #[cfg(target_os = "linux")]
-pub fn mkdev(major: u64, minor: u64) -> dev_t {
-    (major << MINORBITS) | minor
+pub fn major(dev: dev_t) -> u64 {
+    ((dev >> 32) & 0xfffff000) |
+    ((dev >> 8) & 0x00000fff)
+}
+
+/// Extracts the minor number from a device number created by `mkdev`.
+#[cfg(target_os = "linux")]
+pub fn minor(dev: dev_t) -> u64 {
+    ((dev >> 12) & 0xffffff00) |
+    (dev & 0x000000ff)
 }
 
 pub fn umask(mode: Mode) -> Mode {
@@ -72,6 +196,174 @@ pub fn umask(mode: Mode) -> Mode {
     Mode::from_bits(prev).expect("[BUG] umask returned invalid Mode")
 }
 
+/// Creates a directory at `path`, with `mode` masked by the umask.
+pub fn mkdir<P: ?Sized + NixPath>(path: &P, mode: Mode) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe { ffi::mkdir(cstr.as_ptr(), mode.bits() as mode_t) }
+    }));
+    from_ffi(res)
+}
+
+/// Creates a named pipe at `path`, with `mode` masked by the umask.
+pub fn mkfifo<P: ?Sized + NixPath>(path: &P, mode: Mode) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe { ffi::mkfifo(cstr.as_ptr(), mode.bits() as mode_t) }
+    }));
+    from_ffi(res)
+}
+
+/// Changes the permissions of `path`, following symbolic links.
+pub fn chmod<P: ?Sized + NixPath>(path: &P, mode: Mode) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe { ffi::chmod(cstr.as_ptr(), mode.bits() as mode_t) }
+    }));
+    from_ffi(res)
+}
+
+/// Like `chmod`, but for an open file.
+pub fn fchmod(fd: RawFd, mode: Mode) -> Result<()> {
+    from_ffi(unsafe { ffi::fchmod(fd, mode.bits() as mode_t) })
+}
+
+/// A typed view of a `FileStat`.
+///
+/// # Examples
+///
+/// ```
+/// use nix::sys::stat::{stat, FileType, Stat};
+///
+/// let st = Stat::from(stat("/".as_bytes()).unwrap());
+/// assert_eq!(st.file_type(), Some(FileType::Directory));
+/// println!("mode {:o}, modified {:?}", st.mode().bits(), st.modified());
+/// ```
+#[derive(Clone, Copy)]
+pub struct Stat(FileStat);
+
+impl Stat {
+    /// Returns the underlying `libc::stat`.
+    pub fn as_raw(&self) -> &FileStat {
+        &self.0
+    }
+
+    /// Unwraps the underlying `libc::stat`.
+    pub fn into_raw(self) -> FileStat {
+        self.0
+    }
+
+    pub fn file_type(&self) -> Option<FileType> {
+        FileType::from_mode(self.0.st_mode as mode_t)
+    }
+
+    /// The permission bits of the file, including setuid, setgid and sticky.
+    pub fn mode(&self) -> Mode {
+        Mode::from_bits_truncate(self.0.st_mode as mode_t)
+    }
+
+    /// The device containing the file.
+    pub fn dev(&self) -> dev_t {
+        self.0.st_dev as dev_t
+    }
+
+    pub fn ino(&self) -> u64 {
+        self.0.st_ino as u64
+    }
+
+    pub fn nlink(&self) -> u64 {
+        self.0.st_nlink as u64
+    }
+
+    pub fn uid(&self) -> libc::uid_t {
+        self.0.st_uid
+    }
+
+    pub fn gid(&self) -> libc::gid_t {
+        self.0.st_gid
+    }
+
+    /// The device number, for character and block devices.
+    pub fn rdev(&self) -> dev_t {
+        self.0.st_rdev as dev_t
+    }
+
+    pub fn size(&self) -> i64 {
+        self.0.st_size as i64
+    }
+
+    /// The preferred block size for I/O.
+    pub fn blksize(&self) -> i64 {
+        self.0.st_blksize as i64
+    }
+
+    /// The number of 512-byte blocks allocated to the file.
+    pub fn blocks(&self) -> i64 {
+        self.0.st_blocks as i64
+    }
+
+    /// The time of last access.
+    pub fn atime(&self) -> TimeSpec {
+        TimeSpec { tv_sec: self.0.st_atime, tv_nsec: self.0.st_atime_nsec as c_long }
+    }
+
+    /// The time of last modification of the contents.
+    pub fn mtime(&self) -> TimeSpec {
+        TimeSpec { tv_sec: self.0.st_mtime, tv_nsec: self.0.st_mtime_nsec as c_long }
+    }
+
+    /// The time of last status change.
+    pub fn ctime(&self) -> TimeSpec {
+        TimeSpec { tv_sec: self.0.st_ctime, tv_nsec: self.0.st_ctime_nsec as c_long }
+    }
+
+    /// Like `atime`, as a `SystemTime`. Returns `None` if the timestamp is not
+    /// representable as one.
+    pub fn accessed(&self) -> Option<SystemTime> {
+        system_time(self.atime())
+    }
+
+    /// Like `mtime`, as a `SystemTime`. Returns `None` if the timestamp is not
+    /// representable as one.
+    pub fn modified(&self) -> Option<SystemTime> {
+        system_time(self.mtime())
+    }
+
+    /// Like `ctime`, as a `SystemTime`. Returns `None` if the timestamp is not
+    /// representable as one.
+    pub fn changed(&self) -> Option<SystemTime> {
+        system_time(self.ctime())
+    }
+}
+
+impl From<FileStat> for Stat {
+    fn from(stat: FileStat) -> Stat {
+        Stat(stat)
+    }
+}
+
+impl fmt::Debug for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Stat {{ ino: {}, file_type: {:?}, mode: {:o}, size: {} }}",
+               self.ino(), self.file_type(), self.mode().bits(), self.size())
+    }
+}
+
+// The timestamps come from the filesystem, so none of this may panic
+fn system_time(ts: TimeSpec) -> Option<SystemTime> {
+    if ts.tv_nsec < 0 || ts.tv_nsec >= 1_000_000_000 {
+        return None;
+    }
+    let nanos = Duration::new(0, ts.tv_nsec as u32);
+
+    if ts.tv_sec >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(ts.tv_sec as u64))
+                  .and_then(|t| t.checked_add(nanos))
+    } else {
+        // Wrapping keeps the magnitude of the most negative value as a u64
+        let secs = (ts.tv_sec as i64).wrapping_neg() as u64;
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs))
+                  .and_then(|t| t.checked_add(nanos))
+    }
+}
+
 pub fn stat<P: ?Sized + NixPath>(path: &P) -> Result<FileStat> {
     let mut dst = unsafe { mem::uninitialized() };
     let res = try!(path.with_nix_path(|cstr| {
@@ -112,3 +404,315 @@ pub fn fstat(fd: RawFd) -> Result<FileStat> {
 
     Ok(dst)
 }
+
+/// Like `stat`, but relative to the directory `dirfd`. Pass `AT_SYMLINK_NOFOLLOW`
+/// to behave like `lstat`, or `AT_EMPTY_PATH` with an empty path to behave like `fstat`.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/fstatat.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn fstatat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, flags: AtFlags) -> Result<FileStat> {
+    let mut dst = unsafe { mem::uninitialized() };
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe {
+            ffi::fstatat(at_rawfd(dirfd), cstr.as_ptr(), &mut dst as *mut FileStat, flags.bits())
+        }
+    }));
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(dst)
+}
+
+/// Creates a directory `path` relative to `dirfd`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn mkdirat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, mode: Mode) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe { ffi::mkdirat(at_rawfd(dirfd), cstr.as_ptr(), mode.bits() as mode_t) }
+    }));
+    from_ffi(res)
+}
+
+/// Like `mknod`, but relative to the directory `dirfd`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn mknodat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, kind: SFlag, perm: Mode, dev: dev_t) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe {
+            ffi::mknodat(at_rawfd(dirfd), cstr.as_ptr(), kind.bits | perm.bits() as mode_t, dev)
+        }
+    }));
+    from_ffi(res)
+}
+
+/// Changes the permissions of `path` relative to `dirfd`.
+///
+/// Linux does not support changing the mode of a symbolic link, so
+/// `AT_SYMLINK_NOFOLLOW` fails with `ENOTSUP`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn fchmodat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, mode: Mode, flags: AtFlags) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe {
+            ffi::fchmodat(at_rawfd(dirfd), cstr.as_ptr(), mode.bits() as mode_t, flags.bits())
+        }
+    }));
+    from_ffi(res)
+}
+
+/// Passed as a timestamp to `utimensat` or `futimens` to set it to the current time.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub const UTIME_NOW: TimeSpec = TimeSpec { tv_sec: 0, tv_nsec: (1 << 30) - 1 };
+
+/// Passed as a timestamp to `utimensat` or `futimens` to leave it unchanged.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub const UTIME_OMIT: TimeSpec = TimeSpec { tv_sec: 0, tv_nsec: (1 << 30) - 2 };
+
+/// Sets the access and modification times of `path` relative to `dirfd`, with
+/// nanosecond precision. Either can be `UTIME_NOW` or `UTIME_OMIT`.
+///
+/// With `AT_SYMLINK_NOFOLLOW` the times of a symbolic link itself are changed.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/utimensat.2.html)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn utimensat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, atime: &TimeSpec, mtime: &TimeSpec,
+                                      flags: AtFlags) -> Result<()> {
+    let times = [*atime, *mtime];
+    let res = try!(path.with_nix_path(|cstr| {
+        unsafe { ffi::utimensat(at_rawfd(dirfd), cstr.as_ptr(), times.as_ptr(), flags.bits()) }
+    }));
+    from_ffi(res)
+}
+
+/// Like `utimensat`, but for an open file.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn futimens(fd: RawFd, atime: &TimeSpec, mtime: &TimeSpec) -> Result<()> {
+    let times = [*atime, *mtime];
+    from_ffi(unsafe { ffi::futimens(fd, times.as_ptr()) })
+}
+
+#[cfg(target_os = "linux")]
+bitflags!(
+    flags StatxMask: u32 {
+        const STATX_TYPE        = 0x00000001,
+        const STATX_MODE        = 0x00000002,
+        const STATX_NLINK       = 0x00000004,
+        const STATX_UID         = 0x00000008,
+        const STATX_GID         = 0x00000010,
+        const STATX_ATIME       = 0x00000020,
+        const STATX_MTIME       = 0x00000040,
+        const STATX_CTIME       = 0x00000080,
+        const STATX_INO         = 0x00000100,
+        const STATX_SIZE        = 0x00000200,
+        const STATX_BLOCKS      = 0x00000400,
+        const STATX_BASIC_STATS = 0x000007ff,
+        const STATX_BTIME       = 0x00000800,
+        const STATX_MNT_ID      = 0x00001000,
+        const STATX_DIOALIGN    = 0x00002000,
+    }
+);
+
+#[cfg(target_os = "linux")]
+bitflags!(
+    flags StatxAttr: u64 {
+        const STATX_ATTR_COMPRESSED = 0x00000004,
+        const STATX_ATTR_IMMUTABLE  = 0x00000010,
+        const STATX_ATTR_APPEND     = 0x00000020,
+        const STATX_ATTR_NODUMP     = 0x00000040,
+        const STATX_ATTR_ENCRYPTED  = 0x00000800,
+        const STATX_ATTR_AUTOMOUNT  = 0x00001000,
+        const STATX_ATTR_MOUNT_ROOT = 0x00002000,
+        const STATX_ATTR_VERITY     = 0x00100000,
+        const STATX_ATTR_DAX        = 0x00200000,
+    }
+);
+
+/// The result of `statx`.
+///
+/// Fields that were not requested, or that the filesystem could not provide,
+/// are `None`. Only the device numbers and block size are always filled in.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy)]
+pub struct Statx(ffi::statx);
+
+#[cfg(target_os = "linux")]
+impl Statx {
+    /// The fields that the kernel filled in, which may be more or fewer than
+    /// were requested.
+    pub fn mask(&self) -> StatxMask {
+        StatxMask::from_bits_truncate(self.0.stx_mask)
+    }
+
+    fn get<T>(&self, field: StatxMask, value: T) -> Option<T> {
+        if self.mask().contains(field) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn timestamp(&self, field: StatxMask, ts: &ffi::statx_timestamp) -> Option<TimeSpec> {
+        self.get(field, TimeSpec { tv_sec: ts.tv_sec as libc::time_t, tv_nsec: ts.tv_nsec as c_long })
+    }
+
+    pub fn file_type(&self) -> Option<FileType> {
+        if self.mask().contains(STATX_TYPE) {
+            FileType::from_mode(self.0.stx_mode as mode_t)
+        } else {
+            None
+        }
+    }
+
+    /// The permission bits of the file, including setuid, setgid and sticky.
+    pub fn mode(&self) -> Option<Mode> {
+        self.get(STATX_MODE, Mode::from_bits_truncate(self.0.stx_mode as mode_t))
+    }
+
+    pub fn nlink(&self) -> Option<u32> {
+        self.get(STATX_NLINK, self.0.stx_nlink)
+    }
+
+    pub fn uid(&self) -> Option<libc::uid_t> {
+        self.get(STATX_UID, self.0.stx_uid)
+    }
+
+    pub fn gid(&self) -> Option<libc::gid_t> {
+        self.get(STATX_GID, self.0.stx_gid)
+    }
+
+    pub fn ino(&self) -> Option<u64> {
+        self.get(STATX_INO, self.0.stx_ino)
+    }
+
+    pub fn size(&self) -> Option<u64> {
+        self.get(STATX_SIZE, self.0.stx_size)
+    }
+
+    /// The number of 512-byte blocks allocated to the file.
+    pub fn blocks(&self) -> Option<u64> {
+        self.get(STATX_BLOCKS, self.0.stx_blocks)
+    }
+
+    /// The preferred block size for I/O.
+    pub fn blksize(&self) -> u32 {
+        self.0.stx_blksize
+    }
+
+    /// The time of last access.
+    pub fn atime(&self) -> Option<TimeSpec> {
+        self.timestamp(STATX_ATIME, &self.0.stx_atime)
+    }
+
+    /// The time of creation, which many filesystems do not record.
+    pub fn btime(&self) -> Option<TimeSpec> {
+        self.timestamp(STATX_BTIME, &self.0.stx_btime)
+    }
+
+    /// The time of last status change.
+    pub fn ctime(&self) -> Option<TimeSpec> {
+        self.timestamp(STATX_CTIME, &self.0.stx_ctime)
+    }
+
+    /// The time of last modification of the contents.
+    pub fn mtime(&self) -> Option<TimeSpec> {
+        self.timestamp(STATX_MTIME, &self.0.stx_mtime)
+    }
+
+    /// The attributes set on the file. Only those in `attributes_mask` are
+    /// meaningful, the others are always reported as unset.
+    pub fn attributes(&self) -> StatxAttr {
+        StatxAttr::from_bits_truncate(self.0.stx_attributes)
+    }
+
+    /// The attributes that the filesystem supports reporting.
+    pub fn attributes_mask(&self) -> StatxAttr {
+        StatxAttr::from_bits_truncate(self.0.stx_attributes_mask)
+    }
+
+    /// The device containing the file.
+    pub fn dev(&self) -> dev_t {
+        mkdev(self.0.stx_dev_major as u64, self.0.stx_dev_minor as u64)
+    }
+
+    /// The device number, for character and block devices.
+    pub fn rdev(&self) -> dev_t {
+        mkdev(self.0.stx_rdev_major as u64, self.0.stx_rdev_minor as u64)
+    }
+
+    /// The id of the mount containing the file, as in `/proc/self/mountinfo`.
+    pub fn mnt_id(&self) -> Option<u64> {
+        self.get(STATX_MNT_ID, self.0.stx_mnt_id)
+    }
+
+    /// The required memory alignment for `O_DIRECT` I/O. `Some(0)` means that
+    /// the file does not support direct I/O.
+    pub fn dio_mem_align(&self) -> Option<u32> {
+        self.get(STATX_DIOALIGN, self.0.stx_dio_mem_align)
+    }
+
+    /// The required file offset alignment for `O_DIRECT` I/O. `Some(0)` means
+    /// that the file does not support direct I/O.
+    pub fn dio_offset_align(&self) -> Option<u32> {
+        self.get(STATX_DIOALIGN, self.0.stx_dio_offset_align)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl fmt::Debug for Statx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Statx {{ mask: {:?}, ino: {:?}, file_type: {:?}, size: {:?} }}",
+               self.mask(), self.ino(), self.file_type(), self.size())
+    }
+}
+
+/// Whether `statx` needs to synchronise the attributes of a file on a network
+/// filesystem with the server first.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatxSync {
+    /// Do whatever `stat` does (`AT_STATX_SYNC_AS_STAT`).
+    AsStat,
+    /// Always fetch up to date attributes from the server
+    /// (`AT_STATX_FORCE_SYNC`).
+    Force,
+    /// Use whatever is cached locally, even if it is stale
+    /// (`AT_STATX_DONT_SYNC`).
+    DontSync,
+}
+
+#[cfg(target_os = "linux")]
+impl StatxSync {
+    fn bits(&self) -> libc::c_int {
+        match *self {
+            StatxSync::AsStat => 0x0000,
+            StatxSync::Force => 0x2000,
+            StatxSync::DontSync => 0x4000,
+        }
+    }
+}
+
+/// Like `fstatat`, but only retrieves the fields in `mask`, and can report more
+/// than `stat` does, such as the creation time and mount id.
+///
+/// `sync` controls whether network filesystems need to synchronise the
+/// attributes with the server first.
+///
+/// Fails with `ENOSYS` on kernels older than 4.11.
+///
+/// [Further reading](http://man7.org/linux/man-pages/man2/statx.2.html)
+#[cfg(target_os = "linux")]
+pub fn statx<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, flags: AtFlags, sync: StatxSync,
+                                  mask: StatxMask) -> Result<Statx> {
+    use sys::syscall::{syscall, STATX};
+
+    let mut dst: ffi::statx = unsafe { mem::zeroed() };
+    let res = try!(path.with_nix_path(|cstr| unsafe {
+        syscall(STATX, at_rawfd(dirfd), cstr.as_ptr(), flags.bits() | sync.bits(), mask.bits(),
+                &mut dst as *mut ffi::statx)
+    }));
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(Statx(dst))
+}
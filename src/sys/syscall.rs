@@ -12,6 +12,10 @@ mod arch {
 
     pub static SYSPIVOTROOT: Syscall = 155;
     pub static MEMFD_CREATE: Syscall = 319;
+    pub static RENAMEAT2: Syscall = 316;
+    pub static OPENAT2: Syscall = 437;
+    pub static STATX: Syscall = 332;
+    pub static GETTID: Syscall = 186;
 }
 
 #[cfg(target_arch = "x86")]
@@ -22,6 +26,10 @@ mod arch {
 
     pub static SYSPIVOTROOT: Syscall = 217;
     pub static MEMFD_CREATE: Syscall = 356;
+    pub static RENAMEAT2: Syscall = 353;
+    pub static OPENAT2: Syscall = 437;
+    pub static STATX: Syscall = 383;
+    pub static GETTID: Syscall = 224;
 }
 
 #[cfg(target_arch = "aarch64")]
@@ -32,6 +40,10 @@ mod arch {
 
     pub static SYSPIVOTROOT: Syscall = 41;
     pub static MEMFD_CREATE: Syscall = 279;
+    pub static RENAMEAT2: Syscall = 276;
+    pub static OPENAT2: Syscall = 437;
+    pub static STATX: Syscall = 291;
+    pub static GETTID: Syscall = 178;
 }
 
 #[cfg(target_arch = "arm")]
@@ -42,6 +54,10 @@ mod arch {
 
     pub static SYSPIVOTROOT: Syscall = 218;
     pub static MEMFD_CREATE: Syscall = 385;
+    pub static RENAMEAT2: Syscall = 382;
+    pub static OPENAT2: Syscall = 437;
+    pub static STATX: Syscall = 397;
+    pub static GETTID: Syscall = 224;
 }
 
 
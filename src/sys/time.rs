@@ -1,5 +1,493 @@
 use std::{fmt, ops};
-use libc::{time_t, suseconds_t};
+use std::time::Duration;
+use libc::{c_long, time_t, suseconds_t};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use libc::{self, c_int, clockid_t, pid_t, pthread_t};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use errno::Errno;
+use {Error, Result};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use from_ffi;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod ffi {
+    use libc::{c_int, clockid_t, pid_t, pthread_t};
+    use super::TimeSpec;
+
+    extern {
+        pub fn clock_gettime(clk_id: clockid_t, tp: *mut TimeSpec) -> c_int;
+        pub fn clock_getres(clk_id: clockid_t, res: *mut TimeSpec) -> c_int;
+        pub fn clock_settime(clk_id: clockid_t, tp: *const TimeSpec) -> c_int;
+        pub fn clock_nanosleep(clk_id: clockid_t, flags: c_int, request: *const TimeSpec,
+                               remain: *mut TimeSpec) -> c_int;
+        pub fn clock_getcpuclockid(pid: pid_t, clock_id: *mut clockid_t) -> c_int;
+        pub fn pthread_getcpuclockid(thread: pthread_t, clock_id: *mut clockid_t) -> c_int;
+    }
+}
+
+/// A system clock, for `clock_gettime`, `timerfd_create` and friends.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClockId {
+    /// Wall-clock time, affected by `settimeofday` and NTP adjustments.
+    Realtime,
+    /// Time since an unspecified point, never jumps.
+    Monotonic,
+    /// CPU time consumed by the calling process.
+    ProcessCputime,
+    /// CPU time consumed by the calling thread.
+    ThreadCputime,
+    /// Like `Monotonic`, but not subject to NTP frequency adjustments.
+    MonotonicRaw,
+    /// A faster but less precise version of `Realtime`.
+    RealtimeCoarse,
+    /// A faster but less precise version of `Monotonic`.
+    MonotonicCoarse,
+    /// Like `Monotonic`, but also counts time spent suspended.
+    Boottime,
+    /// Like `Realtime`, but timers wake the system from suspend.
+    RealtimeAlarm,
+    /// Like `Boottime`, but timers wake the system from suspend.
+    BoottimeAlarm,
+    /// International Atomic Time, which does not have leap seconds.
+    Tai,
+    /// The CPU-time clock of another process or thread, as returned by
+    /// `clock_getcpuclockid` or `pthread_getcpuclockid`.
+    Cputime(clockid_t),
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl ClockId {
+    pub fn as_raw(&self) -> clockid_t {
+        match *self {
+            ClockId::Realtime => 0,
+            ClockId::Monotonic => 1,
+            ClockId::ProcessCputime => 2,
+            ClockId::ThreadCputime => 3,
+            ClockId::MonotonicRaw => 4,
+            ClockId::RealtimeCoarse => 5,
+            ClockId::MonotonicCoarse => 6,
+            ClockId::Boottime => 7,
+            ClockId::RealtimeAlarm => 8,
+            ClockId::BoottimeAlarm => 9,
+            ClockId::Tai => 11,
+            ClockId::Cputime(id) => id,
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+bitflags!(
+    flags ClockNanosleepFlags: c_int {
+        /// The request is an absolute time on the clock rather than an
+        /// interval relative to now.
+        const TIMER_ABSTIME = 1,
+    }
+);
+
+/// Returns the current time of `clock`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn clock_gettime(clock: ClockId) -> Result<TimeSpec> {
+    let mut ts = TimeSpec::zero();
+    let res = unsafe { ffi::clock_gettime(clock.as_raw(), &mut ts) };
+
+    try!(from_ffi(res));
+
+    Ok(ts)
+}
+
+/// Returns the resolution of `clock`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn clock_getres(clock: ClockId) -> Result<TimeSpec> {
+    let mut ts = TimeSpec::zero();
+    let res = unsafe { ffi::clock_getres(clock.as_raw(), &mut ts) };
+
+    try!(from_ffi(res));
+
+    Ok(ts)
+}
+
+/// Sets the time of `clock`, which requires `CAP_SYS_TIME` for `Realtime`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn clock_settime(clock: ClockId, ts: &TimeSpec) -> Result<()> {
+    let res = unsafe { ffi::clock_settime(clock.as_raw(), ts) };
+
+    from_ffi(res)
+}
+
+/// Sleeps on `clock` for the interval `request`, or until the absolute time
+/// `request` with `TIMER_ABSTIME`.
+///
+/// Returns `None` once the sleep is complete. If a signal handler interrupts
+/// the sleep, returns the time that remains, or the unchanged deadline with
+/// `TIMER_ABSTIME`, so that the sleep can be resumed.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn clock_nanosleep(clock: ClockId, flags: ClockNanosleepFlags, request: &TimeSpec)
+                       -> Result<Option<TimeSpec>> {
+    let mut remain = TimeSpec::zero();
+    let res = unsafe { ffi::clock_nanosleep(clock.as_raw(), flags.bits(), request, &mut remain) };
+
+    match res {
+        0 => Ok(None),
+        libc::EINTR if flags.contains(TIMER_ABSTIME) => Ok(Some(*request)),
+        libc::EINTR => Ok(Some(remain)),
+        errno => Err(Error::Sys(Errno::from_i32(errno))),
+    }
+}
+
+/// Returns the CPU-time clock of the process `pid`, or of the calling
+/// process if `pid` is 0.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn clock_getcpuclockid(pid: pid_t) -> Result<ClockId> {
+    let mut id: clockid_t = 0;
+    let res = unsafe { ffi::clock_getcpuclockid(pid, &mut id) };
+
+    match res {
+        0 => Ok(ClockId::Cputime(id)),
+        errno => Err(Error::Sys(Errno::from_i32(errno))),
+    }
+}
+
+/// Returns the CPU-time clock of the thread `thread` of the calling process.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn pthread_getcpuclockid(thread: pthread_t) -> Result<ClockId> {
+    let mut id: clockid_t = 0;
+    let res = unsafe { ffi::pthread_getcpuclockid(thread, &mut id) };
+
+    match res {
+        0 => Ok(ClockId::Cputime(id)),
+        errno => Err(Error::Sys(Errno::from_i32(errno))),
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct TimeSpec {
+    pub tv_sec: time_t,
+    pub tv_nsec: c_long,
+}
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+impl TimeSpec {
+    #[inline]
+    pub fn zero() -> TimeSpec {
+        TimeSpec::nanoseconds(0)
+    }
+
+    #[inline]
+    pub fn hours(hours: i64) -> TimeSpec {
+        TimeSpec::checked_hours(hours).expect("TimeSpec::hours out of bounds")
+    }
+
+    #[inline]
+    pub fn minutes(minutes: i64) -> TimeSpec {
+        TimeSpec::checked_minutes(minutes).expect("TimeSpec::minutes out of bounds")
+    }
+
+    #[inline]
+    pub fn seconds(seconds: i64) -> TimeSpec {
+        TimeSpec::checked_seconds(seconds)
+            .unwrap_or_else(|| panic!("TimeSpec out of bounds; seconds={}", seconds))
+    }
+
+    #[inline]
+    pub fn milliseconds(milliseconds: i64) -> TimeSpec {
+        TimeSpec::checked_milliseconds(milliseconds).expect("TimeSpec::milliseconds out of bounds")
+    }
+
+    #[inline]
+    pub fn microseconds(microseconds: i64) -> TimeSpec {
+        TimeSpec::checked_microseconds(microseconds).expect("TimeSpec::microseconds out of bounds")
+    }
+
+    /// Makes a new `TimeSpec` with given number of nanoseconds.
+    #[inline]
+    pub fn nanoseconds(nanoseconds: i64) -> TimeSpec {
+        TimeSpec::checked_nanoseconds(nanoseconds).expect("TimeSpec out of bounds")
+    }
+
+    pub fn checked_hours(hours: i64) -> Option<TimeSpec> {
+        hours.checked_mul(SECS_PER_HOUR).and_then(TimeSpec::checked_seconds)
+    }
+
+    pub fn checked_minutes(minutes: i64) -> Option<TimeSpec> {
+        minutes.checked_mul(SECS_PER_MINUTE).and_then(TimeSpec::checked_seconds)
+    }
+
+    pub fn checked_seconds(seconds: i64) -> Option<TimeSpec> {
+        if seconds >= MIN_SECONDS && seconds <= MAX_SECONDS {
+            Some(TimeSpec { tv_sec: seconds as time_t, tv_nsec: 0 })
+        } else {
+            None
+        }
+    }
+
+    pub fn checked_milliseconds(milliseconds: i64) -> Option<TimeSpec> {
+        milliseconds.checked_mul(1_000_000).and_then(TimeSpec::checked_nanoseconds)
+    }
+
+    pub fn checked_microseconds(microseconds: i64) -> Option<TimeSpec> {
+        microseconds.checked_mul(1_000).and_then(TimeSpec::checked_nanoseconds)
+    }
+
+    pub fn checked_nanoseconds(nanoseconds: i64) -> Option<TimeSpec> {
+        TimeSpec::from_parts(0, nanoseconds)
+    }
+
+    /// Converts a `Duration`, failing with `EINVAL` if its seconds do not fit
+    /// in a `time_t`.
+    pub fn from_duration(duration: Duration) -> Result<TimeSpec> {
+        if duration.as_secs() > MAX_SECONDS as u64 {
+            return Err(Error::invalid_argument());
+        }
+
+        Ok(TimeSpec { tv_sec: duration.as_secs() as time_t, tv_nsec: duration.subsec_nanos() as c_long })
+    }
+
+    /// Converts to a `Duration`, failing with `EINVAL` if negative or if
+    /// `tv_nsec` is not within a second.
+    pub fn to_duration(&self) -> Result<Duration> {
+        if self.tv_sec < 0 || self.tv_nsec < 0 || self.tv_nsec as i64 >= NANOS_PER_SEC {
+            return Err(Error::invalid_argument());
+        }
+
+        Ok(Duration::new(self.tv_sec as u64, self.tv_nsec as u32))
+    }
+
+    pub fn num_hours(&self) -> i64 {
+        self.num_seconds() / 3600
+    }
+
+    pub fn num_minutes(&self) -> i64 {
+        self.num_seconds() / 60
+    }
+
+    pub fn num_seconds(&self) -> i64 {
+        if self.tv_sec < 0 && self.tv_nsec > 0 {
+            (self.tv_sec + 1) as i64
+        } else {
+            self.tv_sec as i64
+        }
+    }
+
+    pub fn num_milliseconds(&self) -> i64 {
+        self.num_nanoseconds() / 1_000_000
+    }
+
+    pub fn num_microseconds(&self) -> i64 {
+        self.num_nanoseconds() / 1_000
+    }
+
+    pub fn num_nanoseconds(&self) -> i64 {
+        let secs = self.num_seconds() * NANOS_PER_SEC;
+        let nsec = self.nanos_mod_sec();
+        secs + nsec as i64
+    }
+
+    pub fn checked_neg(self) -> Option<TimeSpec> {
+        TimeSpec::zero().checked_sub(self)
+    }
+
+    pub fn checked_add(self, rhs: TimeSpec) -> Option<TimeSpec> {
+        match (self.parts(), rhs.parts()) {
+            (Some((sec, nsec)), Some((rhs_sec, rhs_nsec))) => {
+                sec.checked_add(rhs_sec).and_then(|sec| TimeSpec::from_parts(sec, nsec + rhs_nsec))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn checked_sub(self, rhs: TimeSpec) -> Option<TimeSpec> {
+        match (self.parts(), rhs.parts()) {
+            (Some((sec, nsec)), Some((rhs_sec, rhs_nsec))) => {
+                sec.checked_sub(rhs_sec).and_then(|sec| TimeSpec::from_parts(sec, nsec - rhs_nsec))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn checked_mul(self, rhs: i32) -> Option<TimeSpec> {
+        // Below a second times an i32, the nanoseconds cannot overflow
+        self.parts().and_then(|(sec, nsec)| {
+            sec.checked_mul(rhs as i64).and_then(|sec| TimeSpec::from_parts(sec, nsec * rhs as i64))
+        })
+    }
+
+    /// Divides, rounding to a nanosecond.
+    pub fn checked_div(self, rhs: i32) -> Option<TimeSpec> {
+        let rhs = rhs as i64;
+
+        // The remainder of the seconds is below an i32, so it can be carried
+        // into the nanoseconds without overflowing
+        self.parts().and_then(|(sec, nsec)| {
+            sec.checked_div(rhs).and_then(|quot| {
+                TimeSpec::from_parts(quot, (sec % rhs * NANOS_PER_SEC + nsec) / rhs)
+            })
+        })
+    }
+
+    // Splits into whole seconds and nanoseconds within a second, carrying any
+    // excess `tv_nsec` set directly through the public fields
+    fn parts(&self) -> Option<(i64, i64)> {
+        let (carry, nsec) = div_mod_floor_64(self.tv_nsec as i64, NANOS_PER_SEC);
+        (self.tv_sec as i64).checked_add(carry).map(|sec| (sec, nsec))
+    }
+
+    // The inverse of `parts`, carrying `nsec` into the seconds
+    fn from_parts(sec: i64, nsec: i64) -> Option<TimeSpec> {
+        let (carry, nsec) = div_mod_floor_64(nsec, NANOS_PER_SEC);
+        sec.checked_add(carry)
+           .and_then(TimeSpec::checked_seconds)
+           .map(|ts| TimeSpec { tv_sec: ts.tv_sec, tv_nsec: nsec as c_long })
+    }
+
+    fn nanos_mod_sec(&self) -> c_long {
+        if self.tv_sec < 0 && self.tv_nsec > 0 {
+            self.tv_nsec - NANOS_PER_SEC as c_long
+        } else {
+            self.tv_nsec
+        }
+    }
+}
+
+impl ops::Neg for TimeSpec {
+    type Output = TimeSpec;
+
+    fn neg(self) -> TimeSpec {
+        self.checked_neg().expect("TimeSpec negation out of bounds")
+    }
+}
+
+impl ops::Add for TimeSpec {
+    type Output = TimeSpec;
+
+    fn add(self, rhs: TimeSpec) -> TimeSpec {
+        self.checked_add(rhs).expect("TimeSpec add out of bounds")
+    }
+}
+
+impl ops::Sub for TimeSpec {
+    type Output = TimeSpec;
+
+    fn sub(self, rhs: TimeSpec) -> TimeSpec {
+        self.checked_sub(rhs).expect("TimeSpec subtract out of bounds")
+    }
+}
+
+impl ops::Mul<i32> for TimeSpec {
+    type Output = TimeSpec;
+
+    fn mul(self, rhs: i32) -> TimeSpec {
+        self.checked_mul(rhs).expect("TimeSpec multiply out of bounds")
+    }
+}
+
+impl ops::Div<i32> for TimeSpec {
+    type Output = TimeSpec;
+
+    fn div(self, rhs: i32) -> TimeSpec {
+        self.checked_div(rhs).expect("TimeSpec divide out of bounds")
+    }
+}
+
+impl fmt::Display for TimeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Unlike `-*self`, this cannot overflow for a `tv_sec` of `i64::MIN`
+        let (sec, nsec, sign) = if self.tv_sec < 0 {
+            (self.num_seconds().wrapping_neg() as u64, -self.nanos_mod_sec(), "-")
+        } else {
+            (self.tv_sec as u64, self.tv_nsec, "")
+        };
+
+        try!(write!(f, "{}", sign));
+
+        if nsec == 0 {
+            if sec == 1 {
+                try!(write!(f, "{} second", sec));
+            } else {
+                try!(write!(f, "{} seconds", sec));
+            }
+        } else if nsec % 1_000_000 == 0 {
+            try!(write!(f, "{}.{:03} seconds", sec, nsec / 1_000_000));
+        } else if nsec % 1_000 == 0 {
+            try!(write!(f, "{}.{:06} seconds", sec, nsec / 1_000));
+        } else {
+            try!(write!(f, "{}.{:09} seconds", sec, nsec));
+        }
+
+        Ok(())
+    }
+}
+
+/// When an armed timer expires, for `timerfd` and POSIX timers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expiration {
+    /// Once, after the given time.
+    OneShot(TimeSpec),
+    /// First after the first time, then repeatedly every second time.
+    IntervalDelayed(TimeSpec, TimeSpec),
+    /// Repeatedly, every given time.
+    Interval(TimeSpec),
+}
+
+impl Expiration {
+    /// Builds an expiration from the `it_value` and `it_interval` halves of an
+    /// `itimerspec`. A zero `value` means the timer is disarmed.
+    pub fn new(value: TimeSpec, interval: TimeSpec) -> Option<Expiration> {
+        if value == TimeSpec::zero() {
+            None
+        } else if interval == TimeSpec::zero() {
+            Some(Expiration::OneShot(value))
+        } else if interval == value {
+            Some(Expiration::Interval(interval))
+        } else {
+            Some(Expiration::IntervalDelayed(value, interval))
+        }
+    }
+
+    /// The time until the first expiration.
+    pub fn value(&self) -> TimeSpec {
+        match *self {
+            Expiration::OneShot(value) |
+            Expiration::IntervalDelayed(value, _) |
+            Expiration::Interval(value) => value,
+        }
+    }
+
+    /// The time between expirations, or zero for a one-shot timer.
+    pub fn interval(&self) -> TimeSpec {
+        match *self {
+            Expiration::OneShot(_) => TimeSpec::zero(),
+            Expiration::IntervalDelayed(_, interval) |
+            Expiration::Interval(interval) => interval,
+        }
+    }
+}
+
+/// The C representation of an optional `Expiration`, as taken and returned
+/// by `timerfd_settime` and `timer_settime`. All zeroes mean disarmed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ITimerSpec {
+    pub it_interval: TimeSpec,
+    pub it_value: TimeSpec,
+}
+
+impl ITimerSpec {
+    pub fn expiration(&self) -> Option<Expiration> {
+        Expiration::new(self.it_value, self.it_interval)
+    }
+}
+
+impl From<Option<Expiration>> for ITimerSpec {
+    fn from(expiration: Option<Expiration>) -> ITimerSpec {
+        match expiration {
+            Some(expiration) => ITimerSpec { it_interval: expiration.interval(), it_value: expiration.value() },
+            None => ITimerSpec { it_interval: TimeSpec::zero(), it_value: TimeSpec::zero() },
+        }
+    }
+}
 
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -12,13 +500,9 @@ const MICROS_PER_SEC: i64 = 1_000_000;
 const SECS_PER_MINUTE: i64 = 60;
 const SECS_PER_HOUR: i64 = 3600;
 
-#[cfg(target_pointer_width = "64")]
-const MAX_SECONDS: i64 = (::std::i64::MAX / MICROS_PER_SEC) - 1;
-
-#[cfg(target_pointer_width = "32")]
-const MAX_SECONDS: i64 = ::std::isize::MAX as i64;
-
-const MIN_SECONDS: i64 = -MAX_SECONDS;
+// The range of `time_t`, for both `TimeSpec` and `TimeVal`
+const MAX_SECONDS: i64 = time_t::max_value() as i64;
+const MIN_SECONDS: i64 = time_t::min_value() as i64;
 
 impl TimeVal {
     #[inline]
@@ -28,40 +512,76 @@ impl TimeVal {
 
     #[inline]
     pub fn hours(hours: i64) -> TimeVal {
-        let secs = hours.checked_mul(SECS_PER_HOUR)
-            .expect("TimeVal::hours ouf of bounds");
-
-        TimeVal::seconds(secs)
+        TimeVal::checked_hours(hours).expect("TimeVal::hours ouf of bounds")
     }
 
     #[inline]
     pub fn minutes(minutes: i64) -> TimeVal {
-        let secs = minutes.checked_mul(SECS_PER_MINUTE)
-            .expect("TimeVal::minutes out of bounds");
-
-        TimeVal::seconds(secs)
+        TimeVal::checked_minutes(minutes).expect("TimeVal::minutes out of bounds")
     }
 
     #[inline]
     pub fn seconds(seconds: i64) -> TimeVal {
-        assert!(seconds >= MIN_SECONDS && seconds <= MAX_SECONDS, "TimeVal out of bounds; seconds={}", seconds);
-        TimeVal { tv_sec: seconds as time_t, tv_usec: 0 }
+        TimeVal::checked_seconds(seconds)
+            .unwrap_or_else(|| panic!("TimeVal out of bounds; seconds={}", seconds))
     }
 
     #[inline]
     pub fn milliseconds(milliseconds: i64) -> TimeVal {
-        let microseconds = milliseconds.checked_mul(1_000)
-            .expect("TimeVal::milliseconds out of bounds");
-
-        TimeVal::microseconds(microseconds)
+        TimeVal::checked_milliseconds(milliseconds).expect("TimeVal::milliseconds out of bounds")
     }
 
     /// Makes a new `TimeVal` with given number of microseconds.
     #[inline]
     pub fn microseconds(microseconds: i64) -> TimeVal {
-        let (secs, micros) = div_mod_floor_64(microseconds, MICROS_PER_SEC);
-        assert!(secs >= MIN_SECONDS && secs <= MAX_SECONDS, "TimeVal out of bounds");
-        TimeVal { tv_sec: secs as time_t, tv_usec: micros as suseconds_t }
+        TimeVal::checked_microseconds(microseconds).expect("TimeVal out of bounds")
+    }
+
+    pub fn checked_hours(hours: i64) -> Option<TimeVal> {
+        hours.checked_mul(SECS_PER_HOUR).and_then(TimeVal::checked_seconds)
+    }
+
+    pub fn checked_minutes(minutes: i64) -> Option<TimeVal> {
+        minutes.checked_mul(SECS_PER_MINUTE).and_then(TimeVal::checked_seconds)
+    }
+
+    pub fn checked_seconds(seconds: i64) -> Option<TimeVal> {
+        if seconds >= MIN_SECONDS && seconds <= MAX_SECONDS {
+            Some(TimeVal { tv_sec: seconds as time_t, tv_usec: 0 })
+        } else {
+            None
+        }
+    }
+
+    pub fn checked_milliseconds(milliseconds: i64) -> Option<TimeVal> {
+        milliseconds.checked_mul(1_000).and_then(TimeVal::checked_microseconds)
+    }
+
+    pub fn checked_microseconds(microseconds: i64) -> Option<TimeVal> {
+        TimeVal::from_parts(0, microseconds)
+    }
+
+    /// Converts a `Duration`, failing with `EINVAL` if its seconds do not fit
+    /// in a `time_t` or it has sub-microsecond precision that would be lost.
+    pub fn from_duration(duration: Duration) -> Result<TimeVal> {
+        if duration.as_secs() > MAX_SECONDS as u64 || duration.subsec_nanos() % 1_000 != 0 {
+            return Err(Error::invalid_argument());
+        }
+
+        Ok(TimeVal {
+            tv_sec: duration.as_secs() as time_t,
+            tv_usec: (duration.subsec_nanos() / 1_000) as suseconds_t,
+        })
+    }
+
+    /// Converts to a `Duration`, failing with `EINVAL` if negative or if
+    /// `tv_usec` is not within a second.
+    pub fn to_duration(&self) -> Result<Duration> {
+        if self.tv_sec < 0 || self.tv_usec < 0 || self.tv_usec as i64 >= MICROS_PER_SEC {
+            return Err(Error::invalid_argument());
+        }
+
+        Ok(Duration::new(self.tv_sec as u64, self.tv_usec as u32 * 1_000))
     }
 
     pub fn num_hours(&self) -> i64 {
@@ -90,6 +610,59 @@ impl TimeVal {
         secs + usec as i64
     }
 
+    pub fn checked_neg(self) -> Option<TimeVal> {
+        TimeVal::zero().checked_sub(self)
+    }
+
+    pub fn checked_add(self, rhs: TimeVal) -> Option<TimeVal> {
+        match (self.parts(), rhs.parts()) {
+            (Some((sec, usec)), Some((rhs_sec, rhs_usec))) => {
+                sec.checked_add(rhs_sec).and_then(|sec| TimeVal::from_parts(sec, usec + rhs_usec))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn checked_sub(self, rhs: TimeVal) -> Option<TimeVal> {
+        match (self.parts(), rhs.parts()) {
+            (Some((sec, usec)), Some((rhs_sec, rhs_usec))) => {
+                sec.checked_sub(rhs_sec).and_then(|sec| TimeVal::from_parts(sec, usec - rhs_usec))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn checked_mul(self, rhs: i32) -> Option<TimeVal> {
+        self.parts().and_then(|(sec, usec)| {
+            sec.checked_mul(rhs as i64).and_then(|sec| TimeVal::from_parts(sec, usec * rhs as i64))
+        })
+    }
+
+    /// Divides, rounding to a microsecond.
+    pub fn checked_div(self, rhs: i32) -> Option<TimeVal> {
+        let rhs = rhs as i64;
+
+        self.parts().and_then(|(sec, usec)| {
+            sec.checked_div(rhs).and_then(|quot| {
+                TimeVal::from_parts(quot, (sec % rhs * MICROS_PER_SEC + usec) / rhs)
+            })
+        })
+    }
+
+    // Splits into whole seconds and microseconds within a second, like
+    // `TimeSpec::parts`
+    fn parts(&self) -> Option<(i64, i64)> {
+        let (carry, usec) = div_mod_floor_64(self.tv_usec as i64, MICROS_PER_SEC);
+        (self.tv_sec as i64).checked_add(carry).map(|sec| (sec, usec))
+    }
+
+    fn from_parts(sec: i64, usec: i64) -> Option<TimeVal> {
+        let (carry, usec) = div_mod_floor_64(usec, MICROS_PER_SEC);
+        sec.checked_add(carry)
+           .and_then(TimeVal::checked_seconds)
+           .map(|tv| TimeVal { tv_sec: tv.tv_sec, tv_usec: usec as suseconds_t })
+    }
+
     fn micros_mod_sec(&self) -> suseconds_t {
         if self.tv_sec < 0 && self.tv_usec > 0 {
             self.tv_usec - MICROS_PER_SEC as suseconds_t
@@ -103,7 +676,7 @@ impl ops::Neg for TimeVal {
     type Output = TimeVal;
 
     fn neg(self) -> TimeVal {
-        TimeVal::microseconds(-self.num_microseconds())
+        self.checked_neg().expect("TimeVal negation out of bounds")
     }
 }
 
@@ -111,8 +684,7 @@ impl ops::Add for TimeVal {
     type Output = TimeVal;
 
     fn add(self, rhs: TimeVal) -> TimeVal {
-        TimeVal::microseconds(
-            self.num_microseconds() + rhs.num_microseconds())
+        self.checked_add(rhs).expect("TimeVal add out of bounds")
     }
 }
 
@@ -120,8 +692,7 @@ impl ops::Sub for TimeVal {
     type Output = TimeVal;
 
     fn sub(self, rhs: TimeVal) -> TimeVal {
-        TimeVal::microseconds(
-            self.num_microseconds() - rhs.num_microseconds())
+        self.checked_sub(rhs).expect("TimeVal subtract out of bounds")
     }
 }
 
@@ -129,10 +700,7 @@ impl ops::Mul<i32> for TimeVal {
     type Output = TimeVal;
 
     fn mul(self, rhs: i32) -> TimeVal {
-        let usec = self.num_microseconds().checked_mul(rhs as i64)
-            .expect("TimeVal multiply out of bounds");
-
-        TimeVal::microseconds(usec)
+        self.checked_mul(rhs).expect("TimeVal multiply out of bounds")
     }
 }
 
@@ -140,33 +708,31 @@ impl ops::Div<i32> for TimeVal {
     type Output = TimeVal;
 
     fn div(self, rhs: i32) -> TimeVal {
-        let usec = self.num_microseconds() / rhs as i64;
-        TimeVal::microseconds(usec)
+        self.checked_div(rhs).expect("TimeVal divide out of bounds")
     }
 }
 
 impl fmt::Display for TimeVal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let (abs, sign) = if self.tv_sec < 0 {
-            (-*self, "-")
+        // Unlike `-*self`, this cannot overflow for a `tv_sec` of `i64::MIN`
+        let (sec, usec, sign) = if self.tv_sec < 0 {
+            (self.num_seconds().wrapping_neg() as u64, -self.micros_mod_sec(), "-")
         } else {
-            (*self, "")
+            (self.tv_sec as u64, self.tv_usec, "")
         };
 
-        let sec = abs.tv_sec;
-
         try!(write!(f, "{}", sign));
 
-        if abs.tv_usec == 0 {
-            if abs.tv_sec == 1 {
+        if usec == 0 {
+            if sec == 1 {
                 try!(write!(f, "{} second", sec));
             } else {
                 try!(write!(f, "{} seconds", sec));
             }
-        } else if abs.tv_usec % 1000 == 0 {
-            try!(write!(f, "{}.{:03} seconds", sec, abs.tv_usec / 1000));
+        } else if usec % 1000 == 0 {
+            try!(write!(f, "{}.{:03} seconds", sec, usec / 1000));
         } else {
-            try!(write!(f, "{}.{:06} seconds", sec, abs.tv_usec));
+            try!(write!(f, "{}.{:06} seconds", sec, usec));
         }
 
         Ok(())
@@ -203,7 +769,9 @@ fn div_rem_64(this: i64, other: i64) -> (i64, i64) {
 
 #[cfg(test)]
 mod test {
-    use super::TimeVal;
+    use super::{TimeSpec, TimeVal};
+    use std::time::Duration;
+    use std::i64;
 
     #[test]
     pub fn test_time_val() {
@@ -229,4 +797,118 @@ mod test {
         assert_eq!(TimeVal::microseconds(42).to_string(), "0.000042 seconds");
         assert_eq!(TimeVal::seconds(-86401).to_string(), "-86401 seconds");
     }
+
+    #[test]
+    pub fn test_time_val_checked() {
+        assert_eq!(TimeVal::checked_minutes(i64::MAX), None);
+        assert_eq!(TimeVal::checked_hours(i64::MAX / 2), None);
+        assert_eq!(TimeVal::seconds(1).checked_mul(i32::max_value()), Some(TimeVal::seconds(2147483647)));
+        assert_eq!(TimeVal::seconds(1).checked_div(0), None);
+        assert_eq!(TimeVal::seconds(3).checked_sub(TimeVal::seconds(5)), Some(TimeVal::seconds(-2)));
+    }
+
+    #[test]
+    pub fn test_time_val_duration() {
+        let tv = TimeVal::seconds(3) + TimeVal::microseconds(250);
+        assert_eq!(tv.to_duration().unwrap(), Duration::new(3, 250_000));
+        assert_eq!(TimeVal::from_duration(Duration::new(3, 250_000)).unwrap(), tv);
+
+        assert!(TimeVal::seconds(-1).to_duration().is_err());
+        assert!(TimeVal::from_duration(Duration::new(0, 1)).is_err());
+        assert!(TimeVal::from_duration(Duration::new(u64::max_value(), 0)).is_err());
+    }
+
+    #[test]
+    pub fn test_time_spec() {
+        assert!(TimeSpec::seconds(1) != TimeSpec::zero());
+        assert_eq!(TimeSpec::seconds(1) + TimeSpec::seconds(2), TimeSpec::seconds(3));
+        assert_eq!(TimeSpec::minutes(3) + TimeSpec::seconds(2),
+                   TimeSpec::seconds(182));
+        assert_eq!(TimeSpec::milliseconds(1500).num_microseconds(), 1_500_000);
+        assert_eq!(TimeSpec::hours(2).num_minutes(), 120);
+        assert_eq!(TimeSpec::seconds(3) * 2 / 4, TimeSpec::milliseconds(1500));
+    }
+
+    #[test]
+    pub fn test_time_spec_neg() {
+        let a = TimeSpec::seconds(1) + TimeSpec::nanoseconds(123);
+        let b = TimeSpec::seconds(-1) + TimeSpec::nanoseconds(-123);
+
+        assert_eq!(a, -b);
+        assert_eq!(b.num_nanoseconds(), -1_000_000_123);
+    }
+
+    #[test]
+    pub fn test_time_spec_fmt() {
+        assert_eq!(TimeSpec::zero().to_string(), "0 seconds");
+        assert_eq!(TimeSpec::seconds(42).to_string(), "42 seconds");
+        assert_eq!(TimeSpec::milliseconds(42).to_string(), "0.042 seconds");
+        assert_eq!(TimeSpec::microseconds(42).to_string(), "0.000042 seconds");
+        assert_eq!(TimeSpec::nanoseconds(42).to_string(), "0.000000042 seconds");
+        assert_eq!(TimeSpec::seconds(-86401).to_string(), "-86401 seconds");
+    }
+
+    #[test]
+    pub fn test_time_spec_duration() {
+        let ts = TimeSpec::seconds(3) + TimeSpec::nanoseconds(7);
+        assert_eq!(ts.to_duration().unwrap(), Duration::new(3, 7));
+        assert_eq!(TimeSpec::from_duration(Duration::new(3, 7)).unwrap(), ts);
+
+        assert!(TimeSpec::nanoseconds(-1).to_duration().is_err());
+        assert!(TimeSpec::from_duration(Duration::new(u64::max_value(), 0)).is_err());
+        assert!(TimeSpec { tv_sec: 0, tv_nsec: 1_000_000_000 }.to_duration().is_err());
+        assert!(TimeSpec { tv_sec: 0, tv_nsec: -1 }.to_duration().is_err());
+    }
+
+    // Only a 64-bit time_t holds values this large
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    pub fn test_time_spec_checked_overflow() {
+        // Out of range for nanosecond arithmetic, but not for a time_t
+        let far = TimeSpec::seconds(20_000_000_000);
+        assert_eq!(far + TimeSpec::nanoseconds(1), TimeSpec { tv_sec: 20_000_000_000, tv_nsec: 1 });
+        assert_eq!(TimeSpec::seconds(1) - far, TimeSpec::seconds(-19_999_999_999));
+        assert_eq!(far * 3 / 4, TimeSpec::seconds(15_000_000_000));
+        assert_eq!(-far, TimeSpec::seconds(-20_000_000_000));
+        assert_eq!(TimeSpec::from_duration(Duration::new(20_000_000_000, 7)).unwrap().to_duration().unwrap(),
+                   Duration::new(20_000_000_000, 7));
+
+        // Only the limits of time_t itself overflow
+        let max = TimeSpec { tv_sec: i64::MAX, tv_nsec: 999_999_999 };
+        let min = TimeSpec { tv_sec: i64::MIN, tv_nsec: 0 };
+        assert_eq!(TimeSpec::checked_seconds(i64::MAX), Some(TimeSpec { tv_sec: i64::MAX, tv_nsec: 0 }));
+        assert_eq!(max.checked_add(TimeSpec::nanoseconds(1)), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!((-max).checked_sub(TimeSpec::nanoseconds(1)), Some(min));
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(min.checked_div(-1), None);
+        assert_eq!(TimeSpec::from_duration(Duration::new(i64::MAX as u64, 0)).unwrap().tv_sec, i64::MAX);
+        assert!(TimeSpec::from_duration(Duration::new(i64::MAX as u64 + 1, 0)).is_err());
+
+        // Excess nanoseconds set through the fields are carried
+        assert_eq!(TimeSpec { tv_sec: 1, tv_nsec: 2_500_000_000 } + TimeSpec::zero(),
+                   TimeSpec::milliseconds(3500));
+
+        assert_eq!(min.to_string(), "-9223372036854775808 seconds");
+        assert_eq!(TimeSpec { tv_sec: -2, tv_nsec: 500_000_000 }.to_string(), "-1.500 seconds");
+    }
+
+    // Only a 64-bit time_t holds values this large
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    pub fn test_time_val_checked_overflow() {
+        let far = TimeVal::seconds(20_000_000_000_000);
+        assert_eq!(far + TimeVal::microseconds(1), TimeVal { tv_sec: 20_000_000_000_000, tv_usec: 1 });
+        assert_eq!(-far, TimeVal::seconds(-20_000_000_000_000));
+
+        let min = TimeVal { tv_sec: i64::MIN, tv_usec: 0 };
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(TimeVal { tv_sec: i64::MAX, tv_usec: 999_999 }.checked_add(TimeVal::microseconds(1)), None);
+
+        assert!(TimeVal { tv_sec: 0, tv_usec: 1_000_000 }.to_duration().is_err());
+        assert!(TimeVal { tv_sec: 0, tv_usec: -1 }.to_duration().is_err());
+
+        assert_eq!(min.to_string(), "-9223372036854775808 seconds");
+        assert_eq!(TimeVal { tv_sec: i64::MIN, tv_usec: 500_000 }.to_string(), "-9223372036854775807.500 seconds");
+    }
 }
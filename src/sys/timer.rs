@@ -0,0 +1,155 @@
+//! POSIX per-process timers.
+//!
+//! A timer reports its expirations as described by a `SigEvent`. To consume
+//! them in an event loop, block the notification signal and read it from a
+//! `sys::signalfd`, where `ssi_overrun` counts the expirations that were
+//! merged into a single signal.
+//!
+//! See [the timer_create man page for more information](http://man7.org/linux/man-pages/man2/timer_create.2.html)
+use libc::{c_int, c_void};
+use {Error, Result, from_ffi};
+use errno::Errno;
+use sys::signal::SigEvent;
+use sys::time::ITimerSpec;
+pub use sys::time::{ClockId, Expiration, TimeSpec};
+
+use std::mem;
+
+mod ffi {
+    use libc::{c_int, clockid_t};
+    use sys::signal::sigevent;
+    use sys::time::ITimerSpec;
+    use super::timer_t;
+
+    extern {
+        pub fn timer_create(clockid: clockid_t, sevp: *const sigevent, timerid: *mut timer_t) -> c_int;
+        pub fn timer_settime(timerid: timer_t, flags: c_int, new_value: *const ITimerSpec,
+                             old_value: *mut ITimerSpec) -> c_int;
+        pub fn timer_gettime(timerid: timer_t, curr_value: *mut ITimerSpec) -> c_int;
+        pub fn timer_getoverrun(timerid: timer_t) -> c_int;
+        pub fn timer_delete(timerid: timer_t) -> c_int;
+    }
+}
+
+#[allow(non_camel_case_types)]
+pub type timer_t = *mut c_void;
+
+bitflags!{
+    flags TimerSetTimeFlags: c_int {
+        /// The expiration is an absolute time on the timer's clock rather than
+        /// relative to now.
+        const TIMER_ABSTIME = 1,
+    }
+}
+
+/// Creates a new, disarmed timer on `clockid` that notifies as described by
+/// `sevent`.
+pub fn timer_create(clockid: ClockId, sevent: &SigEvent) -> Result<timer_t> {
+    let mut timerid: timer_t = unsafe { mem::uninitialized() };
+
+    let res = unsafe { ffi::timer_create(clockid.as_raw(), sevent.as_ref(), &mut timerid) };
+
+    try!(from_ffi(res));
+
+    Ok(timerid)
+}
+
+/// Arms the timer with `expiration`, or disarms it if `None`, and returns the
+/// previous setting.
+pub fn timer_settime(timerid: timer_t, flags: TimerSetTimeFlags, expiration: Option<Expiration>)
+                     -> Result<Option<Expiration>> {
+    let new = ITimerSpec::from(expiration);
+    let mut old: ITimerSpec = unsafe { mem::uninitialized() };
+
+    let res = unsafe { ffi::timer_settime(timerid, flags.bits(), &new, &mut old) };
+
+    try!(from_ffi(res));
+
+    Ok(old.expiration())
+}
+
+/// Returns the time until the next expiration, and the interval, or `None` if
+/// the timer is disarmed.
+pub fn timer_gettime(timerid: timer_t) -> Result<Option<Expiration>> {
+    let mut curr: ITimerSpec = unsafe { mem::uninitialized() };
+
+    let res = unsafe { ffi::timer_gettime(timerid, &mut curr) };
+
+    try!(from_ffi(res));
+
+    Ok(curr.expiration())
+}
+
+/// Returns the number of extra expirations that occurred while the last
+/// notification signal was pending.
+pub fn timer_getoverrun(timerid: timer_t) -> Result<c_int> {
+    let res = unsafe { ffi::timer_getoverrun(timerid) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(res)
+}
+
+/// Deletes the timer, disarming it first if needed.
+pub fn timer_delete(timerid: timer_t) -> Result<()> {
+    let res = unsafe { ffi::timer_delete(timerid) };
+
+    from_ffi(res)
+}
+
+/// An owned POSIX timer, deleted when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use nix::sys::signal::SigEvent;
+/// use nix::sys::timer::*;
+///
+/// let timer = Timer::new(ClockId::Monotonic, &SigEvent::none()).unwrap();
+/// timer.set(Expiration::OneShot(TimeSpec::seconds(1)), TimerSetTimeFlags::empty()).unwrap();
+///
+/// assert!(timer.get().unwrap().is_some());
+/// ```
+#[derive(Debug)]
+pub struct Timer(timer_t);
+
+// The timer ID is a process-wide handle rather than a pointer into memory
+unsafe impl Send for Timer {}
+
+impl Timer {
+    pub fn new(clockid: ClockId, sevent: &SigEvent) -> Result<Timer> {
+        timer_create(clockid, sevent).map(Timer)
+    }
+
+    /// Arms the timer, replacing any previous setting.
+    pub fn set(&self, expiration: Expiration, flags: TimerSetTimeFlags) -> Result<()> {
+        timer_settime(self.0, flags, Some(expiration)).map(|_| ())
+    }
+
+    /// Disarms the timer.
+    pub fn unset(&self) -> Result<()> {
+        timer_settime(self.0, TimerSetTimeFlags::empty(), None).map(|_| ())
+    }
+
+    /// See `timer_gettime`.
+    pub fn get(&self) -> Result<Option<Expiration>> {
+        timer_gettime(self.0)
+    }
+
+    /// See `timer_getoverrun`.
+    pub fn overrun(&self) -> Result<c_int> {
+        timer_getoverrun(self.0)
+    }
+
+    pub fn as_raw(&self) -> timer_t {
+        self.0
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let _ = timer_delete(self.0);
+    }
+}
@@ -0,0 +1,161 @@
+//! Interface for the `timerfd` syscalls.
+//!
+//! A timerfd is a timer that delivers its expirations through a file
+//! descriptor, so it can be waited on with `poll`, `select` or `epoll` along
+//! with other descriptors.
+//!
+//! See [the timerfd_create man page for more information](http://man7.org/linux/man-pages/man2/timerfd_create.2.html)
+use libc::c_int;
+use {Error, Result};
+use fd::Fd;
+use unistd;
+use errno::Errno;
+use sys::time::ITimerSpec;
+pub use sys::time::{ClockId, Expiration, TimeSpec};
+
+use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd, FromRawFd};
+use std::mem;
+
+mod ffi {
+    use libc::{c_int, clockid_t};
+    use sys::time::ITimerSpec;
+
+    extern {
+        pub fn timerfd_create(clockid: clockid_t, flags: c_int) -> c_int;
+        pub fn timerfd_settime(fd: c_int, flags: c_int, new_value: *const ITimerSpec,
+                               old_value: *mut ITimerSpec) -> c_int;
+        pub fn timerfd_gettime(fd: c_int, curr_value: *mut ITimerSpec) -> c_int;
+    }
+}
+
+bitflags!{
+    flags TimerFlags: c_int {
+        const TFD_NONBLOCK  = 0o00004000, // O_NONBLOCK
+        const TFD_CLOEXEC   = 0o02000000, // O_CLOEXEC
+    }
+}
+
+bitflags!{
+    flags TimerSetTimeFlags: c_int {
+        /// The expiration is an absolute time on the timer's clock rather than
+        /// relative to now.
+        const TFD_TIMER_ABSTIME        = 1 << 0,
+        /// With `TFD_TIMER_ABSTIME` on a realtime clock, fail reads with
+        /// `ECANCELED` when the clock is set discontinuously.
+        const TFD_TIMER_CANCEL_ON_SET  = 1 << 1,
+    }
+}
+
+/// Creates a new, disarmed timer on `clockid`.
+pub fn timerfd_create(clockid: ClockId, flags: TimerFlags) -> Result<RawFd> {
+    let fd = unsafe { ffi::timerfd_create(clockid.as_raw(), flags.bits()) };
+
+    if fd < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(fd)
+}
+
+/// Like `timerfd_create`, but returns an owned `Fd` that is closed on drop.
+pub fn timerfd_create_owned(clockid: ClockId, flags: TimerFlags) -> Result<Fd> {
+    timerfd_create(clockid, flags).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
+/// Arms the timer with `expiration`, or disarms it if `None`, and returns the
+/// previous setting.
+pub fn timerfd_settime(fd: RawFd, flags: TimerSetTimeFlags, expiration: Option<Expiration>)
+                       -> Result<Option<Expiration>> {
+    let new = ITimerSpec::from(expiration);
+    let mut old: ITimerSpec = unsafe { mem::uninitialized() };
+
+    let res = unsafe { ffi::timerfd_settime(fd, flags.bits(), &new, &mut old) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(old.expiration())
+}
+
+/// Returns the time until the next expiration, and the interval, or `None` if
+/// the timer is disarmed.
+pub fn timerfd_gettime(fd: RawFd) -> Result<Option<Expiration>> {
+    let mut curr: ITimerSpec = unsafe { mem::uninitialized() };
+
+    let res = unsafe { ffi::timerfd_gettime(fd, &mut curr) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(curr.expiration())
+}
+
+/// An owned timerfd, closed when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use nix::sys::timerfd::*;
+///
+/// let timer = TimerFd::new(ClockId::Monotonic, TimerFlags::empty()).unwrap();
+/// timer.set(Expiration::OneShot(TimeSpec::nanoseconds(1_000_000)), TimerSetTimeFlags::empty()).unwrap();
+///
+/// // Blocks for 1ms
+/// assert_eq!(timer.wait().unwrap(), 1);
+/// ```
+#[derive(Debug)]
+pub struct TimerFd(Fd);
+
+impl TimerFd {
+    pub fn new(clockid: ClockId, flags: TimerFlags) -> Result<TimerFd> {
+        timerfd_create_owned(clockid, flags).map(TimerFd)
+    }
+
+    /// Arms the timer, replacing any previous setting.
+    pub fn set(&self, expiration: Expiration, flags: TimerSetTimeFlags) -> Result<()> {
+        timerfd_settime(self.0.as_raw_fd(), flags, Some(expiration)).map(|_| ())
+    }
+
+    /// Disarms the timer.
+    pub fn unset(&self) -> Result<()> {
+        timerfd_settime(self.0.as_raw_fd(), TimerSetTimeFlags::empty(), None).map(|_| ())
+    }
+
+    /// See `timerfd_gettime`.
+    pub fn get(&self) -> Result<Option<Expiration>> {
+        timerfd_gettime(self.0.as_raw_fd())
+    }
+
+    /// Waits for the timer to expire and returns the number of expirations
+    /// since the last `wait`, which is more than 1 if some were missed.
+    ///
+    /// With `TFD_NONBLOCK` this fails with `EAGAIN` instead of blocking.
+    pub fn wait(&self) -> Result<u64> {
+        let mut buffer = [0u8; 8];
+
+        match try!(unistd::read(self.0.as_raw_fd(), &mut buffer)) {
+            8 => Ok(unsafe { mem::transmute(buffer) }),
+            _ => unreachable!("partial read on timerfd"),
+        }
+    }
+}
+
+impl AsRawFd for TimerFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for TimerFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+
+impl FromRawFd for TimerFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> TimerFd {
+        TimerFd(Fd::from_raw_fd(fd))
+    }
+}
@@ -2,13 +2,22 @@ use libc::{pid_t, c_int};
 use errno::Errno;
 use {Error, Result};
 
-use sys::signal;
+use sys::signal::Signal;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use sys::signal::{self, SigInfo};
+use std::convert::TryFrom;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::mem;
 
 mod ffi {
     use libc::{pid_t, c_int};
 
     extern {
         pub fn waitpid(pid: pid_t, status: *mut c_int, options: c_int) -> pid_t;
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        pub fn waitid(idtype: c_int, id: ::libc::id_t, infop: *mut ::sys::signal::signal::siginfo,
+                      options: c_int) -> c_int;
     }
 }
 
@@ -37,21 +46,32 @@ bitflags!(
 
 #[cfg(any(target_os = "linux",
           target_os = "android"))]
-const WSTOPPED: WaitPidFlag = WUNTRACED;
+pub const WSTOPPED: WaitPidFlag = WUNTRACED;
 
 #[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub enum WaitStatus {
     Exited(pid_t, i8),
-    Signaled(pid_t, signal::SigNum, bool),
-    Stopped(pid_t, signal::SigNum),
+    Signaled(pid_t, Signal, bool),
+    Stopped(pid_t, Signal),
+    /// Stopped at a `PTRACE_EVENT_*` event, with the signal and the event.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    PtraceEvent(pid_t, Signal, c_int),
+    /// Stopped at a system call entry or exit, with `PTRACE_O_TRACESYSGOOD`
+    /// set.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    PtraceSyscall(pid_t),
     Continued(pid_t),
+    /// A change that `WaitStatus` cannot describe, with the raw status as
+    /// `waitpid` reports it. For example a child killed by one of the signals
+    /// that the C library keeps for itself, which `Signal` cannot represent.
+    Unknown(pid_t, c_int),
     StillAlive
 }
 
 #[cfg(any(target_os = "linux",
           target_os = "android"))]
 mod status {
-    use sys::signal;
+    use libc::{self, c_int};
 
     pub fn exited(status: i32) -> bool {
         (status & 0x7F) == 0
@@ -65,8 +85,8 @@ mod status {
         ((((status & 0x7f) + 1) as i8) >> 1) > 0
     }
 
-    pub fn term_signal(status: i32) -> signal::SigNum {
-        (status & 0x7f) as signal::SigNum
+    pub fn term_signal(status: i32) -> c_int {
+        (status & 0x7f) as c_int
     }
 
     pub fn dumped_core(status: i32) -> bool {
@@ -77,8 +97,17 @@ mod status {
         (status & 0xff) == 0x7f
     }
 
-    pub fn stop_signal(status: i32) -> signal::SigNum {
-        ((status & 0xFF00) >> 8) as signal::SigNum
+    pub fn stop_signal(status: i32) -> c_int {
+        ((status & 0xFF00) >> 8) as c_int
+    }
+
+    pub fn syscall_stop(status: i32) -> bool {
+        // PTRACE_O_TRACESYSGOOD sets the high bit of the stop signal
+        stop_signal(status) == libc::SIGTRAP | 0x80
+    }
+
+    pub fn stop_additional(status: i32) -> libc::c_int {
+        (status >> 16) as libc::c_int
     }
 
     pub fn continued(status: i32) -> bool {
@@ -89,7 +118,7 @@ mod status {
 #[cfg(any(target_os = "macos",
           target_os = "ios"))]
 mod status {
-    use sys::signal;
+    use libc::c_int;
 
     const WCOREFLAG: i32 = 0x80;
     const WSTOPPED: i32 = 0x7f;
@@ -102,8 +131,8 @@ mod status {
         ((status >> 8) & 0xFF) as i8
     }
 
-    pub fn stop_signal(status: i32) -> signal::SigNum {
-        (status >> 8) as signal::SigNum
+    pub fn stop_signal(status: i32) -> c_int {
+        (status >> 8) as c_int
     }
 
     pub fn continued(status: i32) -> bool {
@@ -122,8 +151,8 @@ mod status {
         wstatus(status) != WSTOPPED && wstatus(status) != 0
     }
 
-    pub fn term_signal(status: i32) -> signal::SigNum {
-        wstatus(status) as signal::SigNum
+    pub fn term_signal(status: i32) -> c_int {
+        wstatus(status) as c_int
     }
 
     pub fn dumped_core(status: i32) -> bool {
@@ -136,7 +165,7 @@ mod status {
           target_os = "dragonfly",
           target_os = "netbsd"))]
 mod status {
-    use sys::signal;
+    use libc::c_int;
 
     const WCOREFLAG: i32 = 0x80;
     const WSTOPPED: i32 = 0x7f;
@@ -149,16 +178,16 @@ mod status {
         wstatus(status) == WSTOPPED
     }
 
-    pub fn stop_signal(status: i32) -> signal::SigNum {
-        (status >> 8) as signal::SigNum
+    pub fn stop_signal(status: i32) -> c_int {
+        (status >> 8) as c_int
     }
 
     pub fn signaled(status: i32) -> bool {
         wstatus(status) != WSTOPPED && wstatus(status) != 0 && status != 0x13
     }
 
-    pub fn term_signal(status: i32) -> signal::SigNum {
-        wstatus(status) as signal::SigNum
+    pub fn term_signal(status: i32) -> c_int {
+        wstatus(status) as c_int
     }
 
     pub fn exited(status: i32) -> bool {
@@ -178,16 +207,43 @@ mod status {
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn decode_stopped(pid: pid_t, status: i32) -> WaitStatus {
+    if status::syscall_stop(status) {
+        return WaitStatus::PtraceSyscall(pid);
+    }
+
+    match (Signal::try_from(status::stop_signal(status)), status::stop_additional(status)) {
+        (Ok(signal), 0) => WaitStatus::Stopped(pid, signal),
+        (Ok(signal), event) => WaitStatus::PtraceEvent(pid, signal, event),
+        (Err(_), _) => WaitStatus::Unknown(pid, status),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn decode_stopped(pid: pid_t, status: i32) -> WaitStatus {
+    match Signal::try_from(status::stop_signal(status)) {
+        Ok(signal) => WaitStatus::Stopped(pid, signal),
+        Err(_) => WaitStatus::Unknown(pid, status),
+    }
+}
+
+// The child has already been reaped, so this must not fail: anything it
+// cannot describe is returned as `Unknown`
 fn decode(pid : pid_t, status: i32) -> WaitStatus {
     if status::exited(status) {
         WaitStatus::Exited(pid, status::exit_status(status))
     } else if status::signaled(status) {
-        WaitStatus::Signaled(pid, status::term_signal(status), status::dumped_core(status))
+        match Signal::try_from(status::term_signal(status)) {
+            Ok(signal) => WaitStatus::Signaled(pid, signal, status::dumped_core(status)),
+            Err(_) => WaitStatus::Unknown(pid, status),
+        }
     } else if status::stopped(status) {
-        WaitStatus::Stopped(pid, status::stop_signal(status))
-    } else {
-        assert!(status::continued(status));
+        decode_stopped(pid, status)
+    } else if status::continued(status) {
         WaitStatus::Continued(pid)
+    } else {
+        WaitStatus::Unknown(pid, status)
     }
 }
 
@@ -215,3 +271,44 @@ pub fn waitpid(pid: pid_t, options: Option<WaitPidFlag>) -> Result<WaitStatus> {
 pub fn wait() -> Result<WaitStatus> {
     waitpid(-1, None)
 }
+
+/// The children that `waitid` waits for.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Id {
+    /// Any child.
+    All,
+    /// The child with the given process ID.
+    Pid(pid_t),
+    /// Any child in the given process group.
+    PGid(pid_t),
+}
+
+/// Waits for a child in `id` to change state, for the changes selected by
+/// `WEXITED`, `WSTOPPED` and `WCONTINUED` in `flags`, and describes the
+/// change as a `SIGCHLD` would.
+///
+/// With `WNOHANG`, returns `None` if no child has changed state yet. With
+/// `WNOWAIT`, the child is left waitable.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn waitid(id: Id, flags: WaitPidFlag) -> Result<Option<SigInfo>> {
+    let (idtype, id) = match id {
+        Id::All => (0, 0),
+        Id::Pid(pid) => (1, pid),
+        Id::PGid(pgid) => (2, pgid),
+    };
+    let mut info: signal::signal::siginfo = unsafe { mem::zeroed() };
+
+    let res = unsafe { ffi::waitid(idtype, id as ::libc::id_t, &mut info, flags.bits()) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    // Nothing is filled in when no child was ready
+    if info.si_signo == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(SigInfo::from(info)))
+}
@@ -0,0 +1,214 @@
+//! Extended attributes
+//!
+//! Names carry a namespace prefix such as `user.`, `trusted.` or `security.`,
+//! and values are arbitrary bytes.
+//!
+//! [Further reading](http://man7.org/linux/man-pages/man7/xattr.7.html)
+use std::cmp;
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStringExt;
+use std::os::unix::io::RawFd;
+use std::ptr;
+
+use libc::{c_int, c_void, size_t, ssize_t};
+
+use {Error, Result, NixPath, from_ffi};
+use errno::Errno;
+
+mod ffi {
+    use libc::{c_char, c_int, c_void, size_t, ssize_t};
+
+    extern {
+        pub fn getxattr(path: *const c_char, name: *const c_char, value: *mut c_void, size: size_t) -> ssize_t;
+        pub fn lgetxattr(path: *const c_char, name: *const c_char, value: *mut c_void, size: size_t) -> ssize_t;
+        pub fn fgetxattr(fd: c_int, name: *const c_char, value: *mut c_void, size: size_t) -> ssize_t;
+
+        pub fn setxattr(path: *const c_char, name: *const c_char, value: *const c_void, size: size_t,
+                        flags: c_int) -> c_int;
+        pub fn lsetxattr(path: *const c_char, name: *const c_char, value: *const c_void, size: size_t,
+                         flags: c_int) -> c_int;
+        pub fn fsetxattr(fd: c_int, name: *const c_char, value: *const c_void, size: size_t,
+                         flags: c_int) -> c_int;
+
+        pub fn listxattr(path: *const c_char, list: *mut c_char, size: size_t) -> ssize_t;
+        pub fn llistxattr(path: *const c_char, list: *mut c_char, size: size_t) -> ssize_t;
+        pub fn flistxattr(fd: c_int, list: *mut c_char, size: size_t) -> ssize_t;
+
+        pub fn removexattr(path: *const c_char, name: *const c_char) -> c_int;
+        pub fn lremovexattr(path: *const c_char, name: *const c_char) -> c_int;
+        pub fn fremovexattr(fd: c_int, name: *const c_char) -> c_int;
+    }
+}
+
+bitflags!(
+    flags XattrFlags: c_int {
+        /// Fail with `EEXIST` if the attribute already exists.
+        const XATTR_CREATE  = 0x1,
+        /// Fail with `ENODATA` if the attribute does not exist yet.
+        const XATTR_REPLACE = 0x2,
+    }
+);
+
+/// Runs `f` with a buffer large enough for the result. The size is probed
+/// first, and the call retried if the value grew in between (`ERANGE`).
+fn read_buffer<F>(mut f: F) -> Result<Vec<u8>>
+    where F: FnMut(*mut c_void, size_t) -> Result<ssize_t>
+{
+    loop {
+        let size = try!(f(ptr::null_mut(), 0));
+        if size < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        // A size of 0 would make the second call another probe
+        let mut buf: Vec<u8> = Vec::with_capacity(cmp::max(size as usize, 1));
+        let res = try!(f(buf.as_mut_ptr() as *mut c_void, buf.capacity() as size_t));
+
+        if res >= 0 {
+            if res as usize > buf.capacity() {
+                continue;
+            }
+
+            unsafe { buf.set_len(res as usize) };
+            return Ok(buf);
+        }
+
+        match Errno::last() {
+            Errno::ERANGE => continue,
+            errno => return Err(Error::Sys(errno)),
+        }
+    }
+}
+
+/// Returns the value of the attribute `name` of `path`, following symbolic links.
+pub fn getxattr<P: ?Sized + NixPath, N: ?Sized + NixPath>(path: &P, name: &N) -> Result<Vec<u8>> {
+    read_buffer(|value, size| {
+        try!(path.with_nix_path(|path| name.with_nix_path(|name| unsafe {
+            ffi::getxattr(path.as_ptr(), name.as_ptr(), value, size)
+        })))
+    })
+}
+
+/// Like `getxattr`, but for a symbolic link itself rather than its target.
+pub fn lgetxattr<P: ?Sized + NixPath, N: ?Sized + NixPath>(path: &P, name: &N) -> Result<Vec<u8>> {
+    read_buffer(|value, size| {
+        try!(path.with_nix_path(|path| name.with_nix_path(|name| unsafe {
+            ffi::lgetxattr(path.as_ptr(), name.as_ptr(), value, size)
+        })))
+    })
+}
+
+/// Like `getxattr`, but for an open file.
+pub fn fgetxattr<N: ?Sized + NixPath>(fd: RawFd, name: &N) -> Result<Vec<u8>> {
+    read_buffer(|value, size| {
+        name.with_nix_path(|name| unsafe {
+            ffi::fgetxattr(fd, name.as_ptr(), value, size)
+        })
+    })
+}
+
+/// Sets the attribute `name` of `path` to `value`, following symbolic links.
+/// With empty `flags` the attribute is created or replaced as needed.
+pub fn setxattr<P: ?Sized + NixPath, N: ?Sized + NixPath>(path: &P, name: &N, value: &[u8],
+                                                         flags: XattrFlags) -> Result<()> {
+    let res = try!(try!(path.with_nix_path(|path| name.with_nix_path(|name| unsafe {
+        ffi::setxattr(path.as_ptr(), name.as_ptr(), value.as_ptr() as *const c_void,
+                      value.len() as size_t, flags.bits())
+    }))));
+    from_ffi(res)
+}
+
+/// Like `setxattr`, but for a symbolic link itself rather than its target.
+pub fn lsetxattr<P: ?Sized + NixPath, N: ?Sized + NixPath>(path: &P, name: &N, value: &[u8],
+                                                          flags: XattrFlags) -> Result<()> {
+    let res = try!(try!(path.with_nix_path(|path| name.with_nix_path(|name| unsafe {
+        ffi::lsetxattr(path.as_ptr(), name.as_ptr(), value.as_ptr() as *const c_void,
+                       value.len() as size_t, flags.bits())
+    }))));
+    from_ffi(res)
+}
+
+/// Like `setxattr`, but for an open file.
+pub fn fsetxattr<N: ?Sized + NixPath>(fd: RawFd, name: &N, value: &[u8], flags: XattrFlags) -> Result<()> {
+    let res = try!(name.with_nix_path(|name| unsafe {
+        ffi::fsetxattr(fd, name.as_ptr(), value.as_ptr() as *const c_void,
+                       value.len() as size_t, flags.bits())
+    }));
+    from_ffi(res)
+}
+
+/// Returns the names of the attributes of `path`, following symbolic links.
+/// Only the attributes that the caller is permitted to see are included.
+pub fn listxattr<P: ?Sized + NixPath>(path: &P) -> Result<XattrNames> {
+    let buf = try!(read_buffer(|list, size| {
+        path.with_nix_path(|path| unsafe {
+            ffi::listxattr(path.as_ptr(), list as *mut _, size)
+        })
+    }));
+    Ok(XattrNames { buf: buf, pos: 0 })
+}
+
+/// Like `listxattr`, but for a symbolic link itself rather than its target.
+pub fn llistxattr<P: ?Sized + NixPath>(path: &P) -> Result<XattrNames> {
+    let buf = try!(read_buffer(|list, size| {
+        path.with_nix_path(|path| unsafe {
+            ffi::llistxattr(path.as_ptr(), list as *mut _, size)
+        })
+    }));
+    Ok(XattrNames { buf: buf, pos: 0 })
+}
+
+/// Like `listxattr`, but for an open file.
+pub fn flistxattr(fd: RawFd) -> Result<XattrNames> {
+    let buf = try!(read_buffer(|list, size| {
+        Ok(unsafe { ffi::flistxattr(fd, list as *mut _, size) })
+    }));
+    Ok(XattrNames { buf: buf, pos: 0 })
+}
+
+/// Removes the attribute `name` of `path`, following symbolic links.
+pub fn removexattr<P: ?Sized + NixPath, N: ?Sized + NixPath>(path: &P, name: &N) -> Result<()> {
+    let res = try!(try!(path.with_nix_path(|path| name.with_nix_path(|name| unsafe {
+        ffi::removexattr(path.as_ptr(), name.as_ptr())
+    }))));
+    from_ffi(res)
+}
+
+/// Like `removexattr`, but for a symbolic link itself rather than its target.
+pub fn lremovexattr<P: ?Sized + NixPath, N: ?Sized + NixPath>(path: &P, name: &N) -> Result<()> {
+    let res = try!(try!(path.with_nix_path(|path| name.with_nix_path(|name| unsafe {
+        ffi::lremovexattr(path.as_ptr(), name.as_ptr())
+    }))));
+    from_ffi(res)
+}
+
+/// Like `removexattr`, but for an open file.
+pub fn fremovexattr<N: ?Sized + NixPath>(fd: RawFd, name: &N) -> Result<()> {
+    let res = try!(name.with_nix_path(|name| unsafe {
+        ffi::fremovexattr(fd, name.as_ptr())
+    }));
+    from_ffi(res)
+}
+
+/// Iterator over the attribute names returned by `listxattr`.
+#[derive(Clone, Debug)]
+pub struct XattrNames {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl Iterator for XattrNames {
+    type Item = OsString;
+
+    fn next(&mut self) -> Option<OsString> {
+        // The names are NUL-terminated and packed back to back
+        let rest = &self.buf[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let len = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        self.pos = cmp::min(self.pos + len + 1, self.buf.len());
+        Some(OsString::from_vec(rest[..len].to_vec()))
+    }
+}
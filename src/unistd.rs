@@ -2,19 +2,24 @@
 //!
 use {Error, Result, NixPath, from_ffi};
 use errno::Errno;
-use fcntl::{fcntl, OFlag, O_NONBLOCK, O_CLOEXEC, FD_CLOEXEC};
+use fd::Fd;
+use fcntl::{fcntl, FcntlRet, OFlag, O_NONBLOCK, O_CLOEXEC, FD_CLOEXEC};
 use fcntl::FcntlArg::{F_SETFD, F_SETFL};
-use libc::{c_char, c_void, c_int, size_t, pid_t, off_t, uid_t, gid_t};
+use libc::{c_char, c_void, c_int, c_uint, size_t, ssize_t, pid_t, off_t, uid_t, gid_t};
+use sys::time::{TimeSpec, TimeVal};
 use std::mem;
-use std::ffi::CString;
-use std::os::unix::io::RawFd;
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::io::{RawFd, FromRawFd};
+use std::path::PathBuf;
+use std::ptr;
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub use self::linux::*;
 
 mod ffi {
     use libc::{c_char, c_int, size_t};
-    pub use libc::{fork, close, read, write, pipe, ftruncate, unlink, setpgid, getegid, geteuid, getgid, getpid, getppid, getuid};
+    pub use libc::{fork, close, read, write, pipe, lseek, ftruncate, unlink, link, symlink, readlink, rename, realpath, chown, fchown, lchown, setpgid, getegid, geteuid, getgid, getpid, getppid, getuid, alarm, pause, sleep};
 
     #[allow(improper_ctypes)]
     extern {
@@ -65,9 +70,72 @@ mod ffi {
         // doc: http://man7.org/linux/man-pages/man2/fsync.2.html
         pub fn fsync(fd: c_int) -> c_int;
         pub fn fdatasync(fd: c_int) -> c_int;
+
+        // get or set value of an interval timer
+        // doc: http://man7.org/linux/man-pages/man2/setitimer.2.html
+        pub fn getitimer(which: c_int, curr_value: *mut super::ITimerVal) -> c_int;
+        pub fn setitimer(which: c_int, new_value: *const super::ITimerVal,
+                         old_value: *mut super::ITimerVal) -> c_int;
+
+        // high-resolution sleep
+        // doc: http://man7.org/linux/man-pages/man2/nanosleep.2.html
+        pub fn nanosleep(req: *const ::sys::time::TimeSpec, rem: *mut ::sys::time::TimeSpec) -> c_int;
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    extern {
+        // doc: http://man7.org/linux/man-pages/man2/unlinkat.2.html
+        pub fn unlinkat(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int;
+
+        // doc: http://man7.org/linux/man-pages/man2/linkat.2.html
+        pub fn linkat(olddirfd: c_int, oldpath: *const c_char,
+                      newdirfd: c_int, newpath: *const c_char, flags: c_int) -> c_int;
+
+        // doc: http://man7.org/linux/man-pages/man2/symlinkat.2.html
+        pub fn symlinkat(target: *const c_char, newdirfd: c_int, linkpath: *const c_char) -> c_int;
+
+        // doc: http://man7.org/linux/man-pages/man2/fchownat.2.html
+        pub fn fchownat(dirfd: c_int, pathname: *const c_char,
+                        owner: ::libc::uid_t, group: ::libc::gid_t, flags: c_int) -> c_int;
+
+        // doc: http://man7.org/linux/man-pages/man2/faccessat.2.html
+        pub fn faccessat(dirfd: c_int, pathname: *const c_char, mode: c_int, flags: c_int) -> c_int;
+
+        // reposition read/write file offset
+        // doc: http://man7.org/linux/man-pages/man3/lseek64.3.html
+        pub fn lseek64(fd: c_int, offset: ::fcntl::loff_t, whence: c_int) -> ::fcntl::loff_t;
+
+        // manipulate file space
+        // doc: http://man7.org/linux/man-pages/man2/fallocate.2.html
+        pub fn fallocate(fd: c_int, mode: c_int, offset: ::libc::off_t, len: ::libc::off_t) -> c_int;
+
+        // predeclare an access pattern for file data
+        // doc: http://man7.org/linux/man-pages/man2/posix_fadvise.2.html
+        pub fn posix_fadvise(fd: c_int, offset: ::libc::off_t, len: ::libc::off_t, advice: c_int) -> c_int;
+
+        // initiate file readahead into page cache
+        // doc: http://man7.org/linux/man-pages/man2/readahead.2.html
+        pub fn readahead(fd: c_int, offset: ::fcntl::loff_t, count: size_t) -> ::libc::ssize_t;
+
+        // sync a file segment with disk
+        // doc: http://man7.org/linux/man-pages/man2/sync_file_range.2.html
+        pub fn sync_file_range(fd: c_int, offset: ::fcntl::loff_t, nbytes: ::fcntl::loff_t, flags: ::libc::c_uint) -> c_int;
+
+        // commit the filesystem containing a file to disk
+        // doc: http://man7.org/linux/man-pages/man2/syncfs.2.html
+        pub fn syncfs(fd: c_int) -> c_int;
     }
 }
 
+bitflags!(
+    flags AccessFlags: c_int {
+        const F_OK = 0,
+        const X_OK = 1,
+        const W_OK = 2,
+        const R_OK = 4,
+    }
+);
+
 #[derive(Clone, Copy)]
 pub enum Fork {
     Parent(pid_t),
@@ -132,6 +200,11 @@ pub fn dup(oldfd: RawFd) -> Result<RawFd> {
     Ok(res)
 }
 
+/// Like `dup`, but returns an owned `Fd` that is closed on drop.
+pub fn dup_owned(oldfd: RawFd) -> Result<Fd> {
+    dup(oldfd).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 #[inline]
 pub fn dup2(oldfd: RawFd, newfd: RawFd) -> Result<RawFd> {
     let res = unsafe { ffi::dup2(oldfd, newfd) };
@@ -143,10 +216,22 @@ pub fn dup2(oldfd: RawFd, newfd: RawFd) -> Result<RawFd> {
     Ok(res)
 }
 
+/// Like `dup2`, but returns an owned `Fd` that is closed on drop. The `Fd`
+/// takes ownership of `newfd`.
+pub fn dup2_owned(oldfd: RawFd, newfd: RawFd) -> Result<Fd> {
+    dup2(oldfd, newfd).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 pub fn dup3(oldfd: RawFd, newfd: RawFd, flags: OFlag) -> Result<RawFd> {
     dup3_polyfill(oldfd, newfd, flags)
 }
 
+/// Like `dup3`, but returns an owned `Fd` that is closed on drop. The `Fd`
+/// takes ownership of `newfd`.
+pub fn dup3_owned(oldfd: RawFd, newfd: RawFd, flags: OFlag) -> Result<Fd> {
+    dup3(oldfd, newfd, flags).map(|fd| unsafe { Fd::from_raw_fd(fd) })
+}
+
 #[inline]
 fn dup3_polyfill(oldfd: RawFd, newfd: RawFd, flags: OFlag) -> Result<RawFd> {
     if oldfd == newfd {
@@ -281,6 +366,13 @@ pub fn pipe() -> Result<(RawFd, RawFd)> {
     }
 }
 
+/// Like `pipe`, but returns owned `Fd`s that are closed on drop.
+pub fn pipe_owned() -> Result<(Fd, Fd)> {
+    let (reader, writer) = try!(pipe());
+
+    unsafe { Ok((Fd::from_raw_fd(reader), Fd::from_raw_fd(writer))) }
+}
+
 pub fn pipe2(flags: OFlag) -> Result<(RawFd, RawFd)> {
     unsafe {
         let mut fds: [c_int; 2] = mem::uninitialized();
@@ -297,8 +389,15 @@ pub fn pipe2(flags: OFlag) -> Result<(RawFd, RawFd)> {
     }
 }
 
+/// Like `pipe2`, but returns owned `Fd`s that are closed on drop.
+pub fn pipe2_owned(flags: OFlag) -> Result<(Fd, Fd)> {
+    let (reader, writer) = try!(pipe2(flags));
+
+    unsafe { Ok((Fd::from_raw_fd(reader), Fd::from_raw_fd(writer))) }
+}
+
 fn pipe2_setflags(fd1: RawFd, fd2: RawFd, flags: OFlag) -> Result<()> {
-    let mut res = Ok(0);
+    let mut res = Ok(FcntlRet::Done);
 
     if flags.contains(O_CLOEXEC) {
         res = res
@@ -322,6 +421,131 @@ fn pipe2_setflags(fd1: RawFd, fd2: RawFd, flags: OFlag) -> Result<()> {
     }
 }
 
+/// The reference point of a file offset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Whence {
+    /// Relative to the start of the file.
+    SeekSet = 0,
+    /// Relative to the current file offset.
+    SeekCur = 1,
+    /// Relative to the end of the file.
+    SeekEnd = 2,
+    /// The next offset at or after the given one that contains data.
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+    SeekData = 3,
+    /// The next offset at or after the given one that is in a hole. The end of
+    /// the file counts as an implicit hole.
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+    SeekHole = 4,
+}
+
+/// Moves the file offset of `fd` to `offset` relative to `whence`, returning the
+/// resulting offset from the start of the file.
+pub fn lseek(fd: RawFd, offset: off_t, whence: Whence) -> Result<off_t> {
+    let res = unsafe { ffi::lseek(fd, offset, whence as c_int) };
+
+    if res < 0 {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    Ok(res)
+}
+
+/// The kind of a region returned by `Extents`.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtentKind {
+    Data,
+    Hole,
+}
+
+/// A contiguous region of a file that is either all data or all hole.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extent {
+    pub kind: ExtentKind,
+    pub offset: off_t,
+    pub len: off_t,
+}
+
+/// Iterator over the data and hole extents of a file, built on `SeekData` and
+/// `SeekHole`.
+///
+/// Filesystems without hole tracking report the whole file as a single data
+/// extent. The iterator moves the file offset of the descriptor, so use
+/// `pread` to read the data extents.
+///
+/// # Examples
+///
+/// ```no_run
+/// use nix::unistd::{Extents, ExtentKind};
+/// use std::fs::File;
+/// use std::os::unix::io::AsRawFd;
+///
+/// let file = File::open("disk.img").unwrap();
+/// for extent in Extents::new(file.as_raw_fd()).unwrap() {
+///     let extent = extent.unwrap();
+///     if extent.kind == ExtentKind::Data {
+///         println!("{} bytes of data at {}", extent.len, extent.offset);
+///     }
+/// }
+/// ```
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+#[derive(Debug)]
+pub struct Extents {
+    fd: RawFd,
+    pos: off_t,
+    end: off_t,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+impl Extents {
+    /// Starts walking the extents of `fd` from the beginning of the file, up to
+    /// its current size.
+    pub fn new(fd: RawFd) -> Result<Extents> {
+        let end = try!(lseek(fd, 0, Whence::SeekEnd));
+        Ok(Extents { fd: fd, pos: 0, end: end })
+    }
+
+    fn next_extent(&mut self) -> Result<Extent> {
+        let data = match lseek(self.fd, self.pos, Whence::SeekData) {
+            Ok(data) => data,
+            // No data past `pos`, the rest of the file is a hole
+            Err(Error::Sys(Errno::ENXIO)) => self.end,
+            Err(e) => return Err(e),
+        };
+
+        let (kind, next) = if data > self.pos {
+            (ExtentKind::Hole, data)
+        } else {
+            (ExtentKind::Data, try!(lseek(self.fd, self.pos, Whence::SeekHole)))
+        };
+
+        // The file may have been truncated or extended in the meantime
+        let next = ::std::cmp::min(next, self.end);
+        let extent = Extent { kind: kind, offset: self.pos, len: next - self.pos };
+        self.pos = next;
+        Ok(extent)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+impl Iterator for Extents {
+    type Item = Result<Extent>;
+
+    fn next(&mut self) -> Option<Result<Extent>> {
+        if self.pos >= self.end {
+            return None;
+        }
+
+        let res = self.next_extent();
+        if res.is_err() {
+            self.pos = self.end;
+        }
+        Some(res)
+    }
+}
+
 pub fn ftruncate(fd: RawFd, len: off_t) -> Result<()> {
     if unsafe { ffi::ftruncate(fd, len) } < 0 {
         Err(Error::Sys(Errno::last()))
@@ -354,6 +578,109 @@ pub fn unlink<P: ?Sized + NixPath>(path: &P) -> Result<()> {
     from_ffi(res)
 }
 
+/// Creates a hard link `new_path` to `old_path`.
+pub fn link<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(old_path: &P1, new_path: &P2) -> Result<()> {
+    let res = try!(try!(old_path.with_nix_path(|old_cstr| {
+        new_path.with_nix_path(|new_cstr| unsafe {
+            ffi::link(old_cstr.as_ptr(), new_cstr.as_ptr())
+        })
+    })));
+    from_ffi(res)
+}
+
+/// Creates a symbolic link `link_path` pointing to `target`. The target does
+/// not need to exist.
+pub fn symlink<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(target: &P1, link_path: &P2) -> Result<()> {
+    let res = try!(try!(target.with_nix_path(|target| {
+        link_path.with_nix_path(|link_path| unsafe {
+            ffi::symlink(target.as_ptr(), link_path.as_ptr())
+        })
+    })));
+    from_ffi(res)
+}
+
+/// Returns the target of the symbolic link `path`.
+pub fn readlink<P: ?Sized + NixPath>(path: &P) -> Result<OsString> {
+    read_link_target(|buf, len| path.with_nix_path(|cstr| unsafe {
+        ffi::readlink(cstr.as_ptr(), buf, len)
+    }))
+}
+
+/// Calls a `readlink`-like function with ever larger buffers, since a target
+/// that fills the whole buffer may have been truncated.
+pub(crate) fn read_link_target<F>(mut f: F) -> Result<OsString>
+    where F: FnMut(*mut c_char, size_t) -> Result<ssize_t>
+{
+    let mut buf: Vec<u8> = Vec::with_capacity(256);
+
+    loop {
+        let res = try!(f(buf.as_mut_ptr() as *mut c_char, buf.capacity() as size_t));
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        let len = res as usize;
+        if len < buf.capacity() {
+            unsafe { buf.set_len(len) };
+            return Ok(OsString::from_vec(buf));
+        }
+
+        // The result filled the whole buffer, so it may have been truncated.
+        let cap = buf.capacity();
+        buf.reserve(cap * 2);
+    }
+}
+
+/// Renames `old_path` to `new_path`, replacing `new_path` if it exists.
+pub fn rename<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(old_path: &P1, new_path: &P2) -> Result<()> {
+    let res = try!(try!(old_path.with_nix_path(|old_cstr| {
+        new_path.with_nix_path(|new_cstr| unsafe {
+            ffi::rename(old_cstr.as_ptr(), new_cstr.as_ptr())
+        })
+    })));
+    from_ffi(res)
+}
+
+/// Returns the canonical, absolute form of `path`, with all symbolic links,
+/// `.` and `..` components resolved. Every component must exist.
+pub fn realpath<P: ?Sized + NixPath>(path: &P) -> Result<PathBuf> {
+    let res = try!(path.with_nix_path(|cstr| unsafe {
+        ffi::realpath(cstr.as_ptr(), ptr::null_mut())
+    }));
+
+    if res.is_null() {
+        return Err(Error::Sys(Errno::last()));
+    }
+
+    // The result is allocated with malloc when no buffer is passed
+    let path = PathBuf::from(OsStr::from_bytes(unsafe { CStr::from_ptr(res) }.to_bytes()));
+    unsafe { ::libc::free(res as *mut c_void) };
+    Ok(path)
+}
+
+/// Changes the owner and group of `path`, following symbolic links. `None`
+/// leaves the corresponding id unchanged.
+pub fn chown<P: ?Sized + NixPath>(path: &P, owner: Option<uid_t>, group: Option<gid_t>) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| unsafe {
+        ffi::chown(cstr.as_ptr(), owner.unwrap_or(!0), group.unwrap_or(!0))
+    }));
+    from_ffi(res)
+}
+
+/// Like `chown`, but for an open file.
+pub fn fchown(fd: RawFd, owner: Option<uid_t>, group: Option<gid_t>) -> Result<()> {
+    from_ffi(unsafe { ffi::fchown(fd, owner.unwrap_or(!0), group.unwrap_or(!0)) })
+}
+
+/// Like `chown`, but changes a symbolic link itself rather than its target.
+pub fn lchown<P: ?Sized + NixPath>(path: &P, owner: Option<uid_t>, group: Option<gid_t>) -> Result<()> {
+    let res = try!(path.with_nix_path(|cstr| unsafe {
+        ffi::lchown(cstr.as_ptr(), owner.unwrap_or(!0), group.unwrap_or(!0))
+    }));
+    from_ffi(res)
+}
+
 #[inline]
 pub fn chroot<P: ?Sized + NixPath>(path: &P) -> Result<()> {
     let res = try!(path.with_nix_path(|cstr| {
@@ -389,6 +716,95 @@ pub fn fdatasync(fd: RawFd) -> Result<()> {
     Ok(())
 }
 
+/// The interval timers of a process, each of which delivers its own signal.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Which {
+    /// Counts real time, delivering `SIGALRM`. Shared with `alarm`.
+    Real = 0,
+    /// Counts user CPU time of the process, delivering `SIGVTALRM`.
+    Virtual = 1,
+    /// Counts user and system CPU time of the process, delivering `SIGPROF`.
+    Prof = 2,
+}
+
+/// The setting of an interval timer. A zero `it_value` disarms the timer, and
+/// a zero `it_interval` makes it expire only once.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ITimerVal {
+    pub it_interval: TimeVal,
+    pub it_value: TimeVal,
+}
+
+/// Returns the current setting of the interval timer `which`, where
+/// `it_value` is the time until the next expiration.
+pub fn getitimer(which: Which) -> Result<ITimerVal> {
+    let mut curr: ITimerVal = unsafe { mem::uninitialized() };
+
+    let res = unsafe { ffi::getitimer(which as c_int, &mut curr) };
+
+    try!(from_ffi(res));
+
+    Ok(curr)
+}
+
+/// Sets the interval timer `which`, and returns its previous setting.
+pub fn setitimer(which: Which, new_value: &ITimerVal) -> Result<ITimerVal> {
+    let mut old: ITimerVal = unsafe { mem::uninitialized() };
+
+    let res = unsafe { ffi::setitimer(which as c_int, new_value, &mut old) };
+
+    try!(from_ffi(res));
+
+    Ok(old)
+}
+
+/// Schedules `SIGALRM` in `seconds`, or cancels the pending alarm if 0.
+///
+/// Returns the seconds that remained until the previous alarm, or `None` if
+/// there was none.
+#[inline]
+pub fn alarm(seconds: c_uint) -> Option<c_uint> {
+    match unsafe { ffi::alarm(seconds) } {
+        0 => None,
+        remaining => Some(remaining),
+    }
+}
+
+/// Suspends the calling thread until a signal handler runs or the process is
+/// terminated.
+#[inline]
+pub fn pause() {
+    unsafe { ffi::pause() };
+}
+
+/// Sleeps for `seconds`, and returns the seconds left unslept if a signal
+/// handler interrupted the sleep.
+#[inline]
+pub fn sleep(seconds: c_uint) -> c_uint {
+    unsafe { ffi::sleep(seconds) }
+}
+
+/// Sleeps for the interval `request`.
+///
+/// Returns `None` once the sleep is complete, or the time that remains if a
+/// signal handler interrupted it, so that the sleep can be resumed.
+pub fn nanosleep(request: &TimeSpec) -> Result<Option<TimeSpec>> {
+    let mut remain = TimeSpec::zero();
+
+    let res = unsafe { ffi::nanosleep(request, &mut remain) };
+
+    if res < 0 {
+        return match Errno::last() {
+            Errno::EINTR => Ok(Some(remain)),
+            errno => Err(Error::Sys(errno)),
+        };
+    }
+
+    Ok(None)
+}
+
 // POSIX requires that getuid, geteuid, getgid, getegid are always successful,
 // so no need to check return value or errno. See:
 //   - http://pubs.opengroup.org/onlinepubs/9699919799/functions/getuid.html
@@ -417,13 +833,24 @@ pub fn getegid() -> gid_t {
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod linux {
-    use sys::syscall::{syscall, SYSPIVOTROOT};
+    use sys::syscall::{syscall, SYSPIVOTROOT, GETTID};
     use errno::Errno;
-    use {Error, Result, NixPath};
+    use fcntl::{AtFlags, at_rawfd, loff_t};
+    use libc::{c_int, c_uint, off_t, pid_t, size_t, uid_t, gid_t};
+    use std::os::unix::io::RawFd;
+    use {Error, Result, NixPath, from_ffi};
+    use super::{ffi, AccessFlags, Whence};
 
     #[cfg(feature = "execvpe")]
     use std::ffi::CString;
 
+    /// Returns the kernel thread ID of the calling thread, as used by
+    /// `SIGEV_THREAD_ID` and `tgkill`.
+    #[inline]
+    pub fn gettid() -> pid_t {
+        unsafe { syscall(GETTID) as pid_t }
+    }
+
     pub fn pivot_root<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(
             new_root: &P1, put_old: &P2) -> Result<()> {
         let res = try!(try!(new_root.with_nix_path(|new_root| {
@@ -459,4 +886,159 @@ mod linux {
 
         Err(Error::Sys(Errno::last()))
     }
+
+    /// Removes the directory entry `path` relative to `dirfd`. With
+    /// `AT_REMOVEDIR` it behaves like `rmdir` rather than `unlink`.
+    pub fn unlinkat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P, flags: AtFlags) -> Result<()> {
+        let res = try!(path.with_nix_path(|cstr| {
+            unsafe { ffi::unlinkat(at_rawfd(dirfd), cstr.as_ptr(), flags.bits()) }
+        }));
+        from_ffi(res)
+    }
+
+    /// Creates a hard link `new_path` (relative to `new_dirfd`) to `old_path`
+    /// (relative to `old_dirfd`). Symbolic links are only dereferenced with
+    /// `AT_SYMLINK_FOLLOW`.
+    pub fn linkat<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(old_dirfd: Option<RawFd>, old_path: &P1,
+                                                             new_dirfd: Option<RawFd>, new_path: &P2,
+                                                             flags: AtFlags) -> Result<()> {
+        let res = try!(try!(old_path.with_nix_path(|old_cstr| {
+            new_path.with_nix_path(|new_cstr| unsafe {
+                ffi::linkat(at_rawfd(old_dirfd), old_cstr.as_ptr(),
+                            at_rawfd(new_dirfd), new_cstr.as_ptr(), flags.bits())
+            })
+        })));
+        from_ffi(res)
+    }
+
+    /// Creates a symbolic link `link_path` relative to `dirfd`, pointing to `target`.
+    pub fn symlinkat<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(target: &P1,
+                                                                dirfd: Option<RawFd>,
+                                                                link_path: &P2) -> Result<()> {
+        let res = try!(try!(target.with_nix_path(|target| {
+            link_path.with_nix_path(|link_path| unsafe {
+                ffi::symlinkat(target.as_ptr(), at_rawfd(dirfd), link_path.as_ptr())
+            })
+        })));
+        from_ffi(res)
+    }
+
+    /// Changes the owner and group of `path` relative to `dirfd`. `None` leaves
+    /// the corresponding id unchanged.
+    pub fn fchownat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P,
+                                         owner: Option<uid_t>, group: Option<gid_t>,
+                                         flags: AtFlags) -> Result<()> {
+        let res = try!(path.with_nix_path(|cstr| unsafe {
+            ffi::fchownat(at_rawfd(dirfd), cstr.as_ptr(),
+                          owner.unwrap_or(!0), group.unwrap_or(!0), flags.bits())
+        }));
+        from_ffi(res)
+    }
+
+    /// Checks whether the calling process can access `path` relative to `dirfd`.
+    /// With `AT_EACCESS` the effective rather than real ids are used.
+    pub fn faccessat<P: ?Sized + NixPath>(dirfd: Option<RawFd>, path: &P,
+                                          mode: AccessFlags, flags: AtFlags) -> Result<()> {
+        let res = try!(path.with_nix_path(|cstr| unsafe {
+            ffi::faccessat(at_rawfd(dirfd), cstr.as_ptr(), mode.bits(), flags.bits())
+        }));
+        from_ffi(res)
+    }
+
+    /// Like `lseek`, but always with 64-bit offsets, even where `off_t` is
+    /// 32 bits wide.
+    pub fn lseek64(fd: RawFd, offset: loff_t, whence: Whence) -> Result<loff_t> {
+        let res = unsafe { ffi::lseek64(fd, offset, whence as c_int) };
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(res)
+    }
+
+    bitflags!(
+        flags FallocateFlags: c_int {
+            const FALLOC_FL_KEEP_SIZE      = 0x01,
+            const FALLOC_FL_PUNCH_HOLE     = 0x02,
+            const FALLOC_FL_NO_HIDE_STALE  = 0x04,
+            const FALLOC_FL_COLLAPSE_RANGE = 0x08,
+            const FALLOC_FL_ZERO_RANGE     = 0x10,
+            const FALLOC_FL_INSERT_RANGE   = 0x20,
+            const FALLOC_FL_UNSHARE_RANGE  = 0x40,
+        }
+    );
+
+    /// Allocates, deallocates or zeroes the byte range `[offset, offset + len)`
+    /// of the file. With empty `flags` this is a plain allocation that extends
+    /// the file size if needed.
+    ///
+    /// `FALLOC_FL_PUNCH_HOLE` must be combined with `FALLOC_FL_KEEP_SIZE`, and
+    /// not every filesystem supports every mode (`EOPNOTSUPP`).
+    pub fn fallocate(fd: RawFd, flags: FallocateFlags, offset: off_t, len: off_t) -> Result<()> {
+        from_ffi(unsafe { ffi::fallocate(fd, flags.bits(), offset, len) })
+    }
+
+    /// The expected access pattern passed to `posix_fadvise`.
+    #[repr(i32)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum PosixFadviseAdvice {
+        /// No particular access pattern (the default).
+        Normal = 0,
+        /// Data will be accessed randomly.
+        Random = 1,
+        /// Data will be accessed sequentially, from lower to higher offsets.
+        Sequential = 2,
+        /// Data will be accessed in the near future.
+        WillNeed = 3,
+        /// Data will not be accessed in the near future.
+        DontNeed = 4,
+        /// Data will be accessed only once.
+        NoReuse = 5,
+    }
+
+    /// Announces how the byte range `[offset, offset + len)` of the file is
+    /// going to be accessed. A `len` of 0 extends to the end of the file.
+    pub fn posix_fadvise(fd: RawFd, offset: off_t, len: off_t, advice: PosixFadviseAdvice) -> Result<()> {
+        // Returns the error number instead of setting errno
+        match unsafe { ffi::posix_fadvise(fd, offset, len, advice as c_int) } {
+            0 => Ok(()),
+            errno => Err(Error::Sys(Errno::from_i32(errno))),
+        }
+    }
+
+    /// Starts reading `count` bytes at `offset` into the page cache, blocking
+    /// until the read has been initiated.
+    pub fn readahead(fd: RawFd, offset: loff_t, count: usize) -> Result<()> {
+        let res = unsafe { ffi::readahead(fd, offset, count as size_t) };
+
+        if res < 0 {
+            return Err(Error::Sys(Errno::last()));
+        }
+
+        Ok(())
+    }
+
+    bitflags!(
+        flags SyncFileRangeFlags: c_uint {
+            const SYNC_FILE_RANGE_WAIT_BEFORE = 0x1,
+            const SYNC_FILE_RANGE_WRITE       = 0x2,
+            const SYNC_FILE_RANGE_WAIT_AFTER  = 0x4,
+        }
+    );
+
+    /// Flushes the dirty pages in the byte range `[offset, offset + nbytes)` of
+    /// the file, as directed by `flags`. An `nbytes` of 0 extends to the end of
+    /// the file.
+    ///
+    /// Unlike `fsync` and `fdatasync` this does not flush metadata, so it
+    /// gives no guarantee that the data survives a crash.
+    pub fn sync_file_range(fd: RawFd, offset: loff_t, nbytes: loff_t, flags: SyncFileRangeFlags) -> Result<()> {
+        from_ffi(unsafe { ffi::sync_file_range(fd, offset, nbytes, flags.bits()) })
+    }
+
+    /// Like `sync`, but only for the filesystem containing the open file `fd`.
+    pub fn syncfs(fd: RawFd) -> Result<()> {
+        from_ffi(unsafe { ffi::syncfs(fd) })
+    }
 }
@@ -4,3 +4,27 @@ mod test_ioctl;
 mod test_wait;
 mod test_select;
 mod test_uio;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod test_signal;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod test_sendfile;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod test_xattr;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod test_inotify;
+
+#[cfg(target_os = "linux")]
+mod test_fanotify;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod test_timer;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod test_timerfd;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod test_time;
@@ -0,0 +1,95 @@
+use nix::sys::fanotify::*;
+use nix::fcntl::{O_RDONLY, O_CLOEXEC};
+use nix::errno::EINVAL;
+use nix::Error::Sys;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::os::unix::io::AsRawFd;
+use std::thread;
+use tempdir::TempDir;
+
+// Creating a group needs CAP_SYS_ADMIN, so these tests are ignored by default;
+// run them as root with `cargo test -- --ignored`. The reporting formats also
+// need a recent kernel, and are skipped when it rejects them.
+fn init(name: &str, class: Class, flags: InitFlags) -> Option<Fanotify> {
+    match Fanotify::init(class, flags, O_RDONLY | O_CLOEXEC) {
+        Err(Sys(EINVAL)) => {
+            println!("skipping {}: the kernel does not support these fanotify flags", name);
+            None
+        }
+        res => Some(res.unwrap()),
+    }
+}
+
+#[test]
+#[ignore]
+fn test_fanotify_permission() {
+    let group = match init("test_fanotify_permission", Class::Content, FAN_CLOEXEC) {
+        Some(group) => group,
+        None => return,
+    };
+
+    let tempdir = TempDir::new("nix-test_fanotify_permission").unwrap();
+    let path = tempdir.path().join("file");
+    File::create(&path).unwrap();
+    group.mark(FAN_MARK_ADD, MarkTarget::Inode, FAN_OPEN_PERM, None, Some(&path)).unwrap();
+
+    for &(response, allowed) in &[(Response::Allow, true), (Response::Deny, false)] {
+        let opener = {
+            let path = path.clone();
+            thread::spawn(move || File::open(&path).map(|_| ()))
+        };
+
+        let events = group.read_events().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.mask, FAN_OPEN_PERM);
+        assert!(event.is_permission());
+        assert!(event.info.is_empty());
+        let fd = event.fd.as_ref().unwrap();
+        group.write_response(fd, response).unwrap();
+
+        let res = opener.join().unwrap();
+        if allowed {
+            res.unwrap();
+        } else {
+            assert_eq!(res.unwrap_err().kind(), ErrorKind::PermissionDenied);
+        }
+    }
+
+    group.mark(FAN_MARK_REMOVE, MarkTarget::Inode, FAN_OPEN_PERM, None, Some(&path)).unwrap();
+    File::open(&path).unwrap();
+}
+
+#[test]
+#[ignore]
+fn test_fanotify_dfid_name() {
+    let group = match init("test_fanotify_dfid_name", Class::Notif, FAN_NONBLOCK | FAN_REPORT_DFID_NAME) {
+        Some(group) => group,
+        None => return,
+    };
+
+    let tempdir = TempDir::new("nix-test_fanotify_dfid_name").unwrap();
+    let dir = File::open(tempdir.path()).unwrap();
+    group.mark(FAN_MARK_ADD | FAN_MARK_ONLYDIR, MarkTarget::Inode, FAN_CREATE | FAN_DELETE | FAN_ONDIR,
+               Some(dir.as_raw_fd()), None::<&[u8]>).unwrap();
+
+    assert!(group.read_events().unwrap().is_empty());
+
+    File::create(tempdir.path().join("created")).unwrap();
+    let events = group.read_events().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].mask, FAN_CREATE);
+    assert!(events[0].fd.is_none());
+
+    assert_eq!(events[0].info.len(), 1);
+    match events[0].info[0] {
+        FanotifyInfo::Fid(ref fid) => {
+            assert_eq!(fid.kind, FidKind::DfidName);
+            assert!(!fid.handle.is_empty());
+            assert_eq!(fid.name, Some(OsString::from("created")));
+        }
+        ref info => panic!("unexpected info record {:?}", info),
+    }
+}
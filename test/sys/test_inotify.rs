@@ -0,0 +1,68 @@
+use nix::sys::inotify::*;
+use nix::poll::{poll, PollFd, POLLIN, EventFlags};
+use nix::sys::epoll::*;
+use nix::unistd::close;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::os::unix::io::AsRawFd;
+use tempdir::TempDir;
+
+#[test]
+fn test_inotify_events() {
+    let tempdir = TempDir::new("nix-test_inotify_events").unwrap();
+    let inotify = Inotify::init(IN_NONBLOCK | IN_CLOEXEC).unwrap();
+    let wd = inotify.add_watch(tempdir.path(), IN_CREATE | IN_MOVE | IN_DELETE).unwrap();
+
+    assert_eq!(inotify.read_events().unwrap(), vec![]);
+
+    File::create(tempdir.path().join("file")).unwrap();
+    fs::rename(tempdir.path().join("file"), tempdir.path().join("renamed")).unwrap();
+    fs::create_dir(tempdir.path().join("dir")).unwrap();
+
+    let mut fds = [PollFd { fd: inotify.as_raw_fd(), events: POLLIN, revents: EventFlags::empty() }];
+    assert_eq!(poll(&mut fds, 1000).unwrap(), 1);
+    assert!(fds[0].revents.contains(POLLIN));
+
+    let events = inotify.read_events().unwrap();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0], InotifyEvent { wd: wd, mask: IN_CREATE, cookie: 0, name: Some(OsString::from("file")) });
+    assert_eq!(events[1].mask, IN_MOVED_FROM);
+    assert_eq!(events[2].mask, IN_MOVED_TO);
+    assert_eq!(events[2].name, Some(OsString::from("renamed")));
+    assert!(events[1].cookie != 0);
+    assert_eq!(events[1].cookie, events[2].cookie);
+    assert_eq!(events[3].mask, IN_CREATE | IN_ISDIR);
+
+    inotify.rm_watch(wd).unwrap();
+    let events = inotify.read_events().unwrap();
+    assert_eq!(events, vec![InotifyEvent { wd: wd, mask: IN_IGNORED, cookie: 0, name: None }]);
+    assert!(inotify.rm_watch(wd).is_err());
+}
+
+#[test]
+fn test_inotify_epoll() {
+    let tempdir = TempDir::new("nix-test_inotify_epoll").unwrap();
+    let path = tempdir.path().join("file");
+    File::create(&path).unwrap();
+
+    let inotify = Inotify::init(IN_NONBLOCK).unwrap();
+    let wd = inotify.add_watch(&path, IN_MODIFY | IN_ATTRIB).unwrap();
+    assert_eq!(inotify.add_watch(&path, IN_MODIFY | IN_MASK_ADD).unwrap(), wd);
+
+    let epfd = epoll_create().unwrap();
+    let event = EpollEvent { events: EPOLLIN, data: 42 };
+    epoll_ctl(epfd, EpollOp::EpollCtlAdd, inotify.as_raw_fd(), &event).unwrap();
+
+    let mut events = [EpollEvent { events: EpollEventKind::empty(), data: 0 }];
+    assert_eq!(epoll_wait(epfd, &mut events, 0).unwrap(), 0);
+
+    fs::set_permissions(&path, fs::metadata(&path).unwrap().permissions()).unwrap();
+    assert_eq!(epoll_wait(epfd, &mut events, 1000).unwrap(), 1);
+    let data = events[0].data;
+    assert_eq!(data, 42);
+
+    let events = inotify.read_events().unwrap();
+    assert_eq!(events, vec![InotifyEvent { wd: wd, mask: IN_ATTRIB, cookie: 0, name: None }]);
+
+    close(epfd).unwrap();
+}
@@ -0,0 +1,35 @@
+use std::fs::File;
+use std::io::Write;
+use std::os::unix::io::AsRawFd;
+
+use libc::off_t;
+use tempdir::TempDir;
+
+use nix::sys::sendfile::sendfile;
+use nix::unistd::{close, pipe, read};
+
+#[test]
+fn test_sendfile() {
+    const CONTENTS: &'static [u8] = b"abcdef123456";
+    let tempdir = TempDir::new("nix-test_sendfile").unwrap();
+    File::create(tempdir.path().join("file")).unwrap().write_all(CONTENTS).unwrap();
+    let tmp = File::open(tempdir.path().join("file")).unwrap();
+
+    let (rd, wr) = pipe().unwrap();
+    let mut offset: off_t = 5;
+    let res = sendfile(wr, tmp.as_raw_fd(), Some(&mut offset), 2).unwrap();
+    assert_eq!(2, res);
+    assert_eq!(7, offset);
+
+    let mut buf = [0u8; 1024];
+    assert_eq!(2, read(rd, &mut buf).unwrap());
+    assert_eq!(b"f1", &buf[0..2]);
+
+    // Without an offset, the file offset is used
+    assert_eq!(3, sendfile(wr, tmp.as_raw_fd(), None, 3).unwrap());
+    assert_eq!(3, read(rd, &mut buf).unwrap());
+    assert_eq!(b"abc", &buf[0..3]);
+
+    close(rd).unwrap();
+    close(wr).unwrap();
+}
@@ -0,0 +1,30 @@
+use nix::sys::signal::*;
+use nix::sys::time::TimeSpec;
+use nix::unistd::getpid;
+
+#[test]
+fn test_sigwait() {
+    // raise sends to this thread only, so blocking here is enough
+    let mut mask = SigSet::empty();
+    mask.add(SIGUSR2).unwrap();
+    mask.thread_block().unwrap();
+
+    assert!(mask.timed_wait(&TimeSpec::milliseconds(10)).unwrap().is_none());
+
+    raise(SIGUSR2).unwrap();
+    assert!(SigSet::pending().unwrap().contains(SIGUSR2).unwrap());
+    assert_eq!(mask.wait(), Ok(SIGUSR2));
+    assert!(!SigSet::pending().unwrap().contains(SIGUSR2).unwrap());
+
+    raise(SIGUSR2).unwrap();
+    let info = mask.wait_info().unwrap();
+    assert_eq!(info.signal(), Ok(SIGUSR2));
+    match info.cause() {
+        SigCause::Kill { pid, .. } => assert_eq!(pid, getpid()),
+        cause => panic!("unexpected cause {:?}", cause),
+    }
+
+    raise(SIGUSR2).unwrap();
+    let info = mask.timed_wait(&TimeSpec::seconds(1)).unwrap().unwrap();
+    assert_eq!(info.signal(), Ok(SIGUSR2));
+}
@@ -0,0 +1,40 @@
+use nix::sys::time::*;
+use nix::unistd::getpid;
+use libc;
+
+#[test]
+fn test_clock_gettime() {
+    let a = clock_gettime(ClockId::Monotonic).unwrap();
+    let b = clock_gettime(ClockId::Monotonic).unwrap();
+    assert!(b >= a);
+
+    assert!(clock_getres(ClockId::Monotonic).unwrap() > TimeSpec::zero());
+    assert!(clock_gettime(ClockId::Realtime).unwrap() > TimeSpec::zero());
+}
+
+#[test]
+fn test_cpu_clocks() {
+    // Burn some CPU time so that the clocks are nonzero
+    let start = clock_gettime(ClockId::ThreadCputime).unwrap();
+    while clock_gettime(ClockId::ThreadCputime).unwrap() - start < TimeSpec::milliseconds(1) {}
+
+    let process = clock_getcpuclockid(getpid()).unwrap();
+    assert!(clock_gettime(process).unwrap() >= TimeSpec::milliseconds(1));
+    assert!(clock_gettime(ClockId::ProcessCputime).unwrap() >= TimeSpec::milliseconds(1));
+
+    let thread = pthread_getcpuclockid(unsafe { libc::pthread_self() }).unwrap();
+    assert!(clock_gettime(thread).unwrap() >= TimeSpec::milliseconds(1));
+}
+
+#[test]
+fn test_clock_nanosleep() {
+    let start = clock_gettime(ClockId::Monotonic).unwrap();
+    assert_eq!(clock_nanosleep(ClockId::Monotonic, ClockNanosleepFlags::empty(),
+                               &TimeSpec::milliseconds(10)).unwrap(), None);
+    let end = clock_gettime(ClockId::Monotonic).unwrap();
+    assert!(end - start >= TimeSpec::milliseconds(10));
+
+    let deadline = end + TimeSpec::milliseconds(10);
+    assert_eq!(clock_nanosleep(ClockId::Monotonic, TIMER_ABSTIME, &deadline).unwrap(), None);
+    assert!(clock_gettime(ClockId::Monotonic).unwrap() >= deadline);
+}
@@ -0,0 +1,81 @@
+use nix::sys::timer::*;
+use nix::sys::signal::SigEvent;
+
+#[test]
+fn test_timer_none() {
+    let timer = Timer::new(ClockId::Monotonic, &SigEvent::none()).unwrap();
+    assert_eq!(timer.get().unwrap(), None);
+
+    timer.set(Expiration::Interval(TimeSpec::seconds(10)), TimerSetTimeFlags::empty()).unwrap();
+    match timer.get().unwrap() {
+        Some(Expiration::IntervalDelayed(remaining, interval)) => {
+            assert!(remaining <= TimeSpec::seconds(10));
+            assert_eq!(interval, TimeSpec::seconds(10));
+        }
+        other => panic!("unexpected timer setting {:?}", other),
+    }
+    assert_eq!(timer.overrun().unwrap(), 0);
+}
+
+#[test]
+fn test_timer_signal() {
+    use nix::sys::signal::{SigCause, SigSet, SIGUSR2};
+    use nix::unistd::gettid;
+
+    // Directed at this thread only, so that other test threads that do not
+    // block the signal are unaffected
+    let mut mask = SigSet::empty();
+    mask.add(SIGUSR2).unwrap();
+    mask.thread_block().unwrap();
+
+    let sev = SigEvent::thread_id(SIGUSR2, gettid()).value(7);
+    let timer = Timer::new(ClockId::Monotonic, &sev).unwrap();
+    timer.set(Expiration::OneShot(TimeSpec::milliseconds(1)), TimerSetTimeFlags::empty()).unwrap();
+
+    let info = mask.timed_wait(&TimeSpec::seconds(5)).unwrap().expect("timer signal not delivered");
+    assert_eq!(info.signal(), Ok(SIGUSR2));
+    match info.cause() {
+        SigCause::Timer { overrun, value } => {
+            assert_eq!(overrun, 0);
+            assert_eq!(value.sival_int(), 7);
+        }
+        cause => panic!("unexpected cause {:?}", cause),
+    }
+}
+
+#[cfg(feature = "signalfd")]
+#[test]
+fn test_timer_signalfd() {
+    use nix::sys::signal::{SigCause, SigInfo, SIGUSR2};
+    use nix::sys::signalfd::*;
+    use nix::unistd::gettid;
+
+    // Directed at this thread only, so that other test threads that do not
+    // block the signal are unaffected
+    let mut mask = SigSet::empty();
+    mask.add(SIGUSR2).unwrap();
+    mask.thread_block().unwrap();
+    let mut sfd = SignalFd::new(&mask).unwrap();
+
+    let sev = SigEvent::thread_id(SIGUSR2, gettid()).value(42);
+    let timer = Timer::new(ClockId::Monotonic, &sev).unwrap();
+    timer.set(Expiration::OneShot(TimeSpec::seconds(10)), TimerSetTimeFlags::empty()).unwrap();
+    timer.unset().unwrap();
+    assert_eq!(timer.get().unwrap(), None);
+
+    timer.set(Expiration::OneShot(TimeSpec::milliseconds(1)), TimerSetTimeFlags::empty()).unwrap();
+
+    let info = sfd.read_signal().unwrap().unwrap();
+    assert_eq!(info.signal().unwrap(), SIGUSR2);
+    assert_eq!({ info.ssi_code }, -2); // SI_TIMER
+    assert_eq!({ info.ssi_ptr }, 42);
+    assert_eq!({ info.ssi_overrun }, 0);
+
+    match SigInfo::from(info).cause() {
+        SigCause::Timer { overrun, value } => {
+            assert_eq!(overrun, 0);
+            assert_eq!(value.sival_int(), 42);
+        }
+        cause => panic!("unexpected cause {:?}", cause),
+    }
+}
@@ -0,0 +1,64 @@
+use nix::sys::timerfd::*;
+use nix::sys::epoll::*;
+use nix::errno::EAGAIN;
+use nix::unistd::close;
+use nix::Error::Sys;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+
+#[test]
+fn test_timerfd_oneshot() {
+    let timer = TimerFd::new(ClockId::Monotonic, TFD_CLOEXEC).unwrap();
+    assert_eq!(timer.get().unwrap(), None);
+
+    timer.set(Expiration::OneShot(TimeSpec::seconds(10)), TimerSetTimeFlags::empty()).unwrap();
+    match timer.get().unwrap() {
+        Some(Expiration::OneShot(remaining)) => assert!(remaining <= TimeSpec::seconds(10)),
+        other => panic!("unexpected timer setting {:?}", other),
+    }
+
+    timer.unset().unwrap();
+    assert_eq!(timer.get().unwrap(), None);
+
+    timer.set(Expiration::OneShot(TimeSpec::nanoseconds(1_000_000)), TimerSetTimeFlags::empty()).unwrap();
+    assert_eq!(timer.wait().unwrap(), 1);
+    assert_eq!(timer.get().unwrap(), None);
+}
+
+#[test]
+fn test_timerfd_interval_epoll() {
+    let timer = TimerFd::new(ClockId::Monotonic, TFD_NONBLOCK).unwrap();
+    assert_eq!(timer.wait().unwrap_err(), Sys(EAGAIN));
+
+    let epfd = epoll_create().unwrap();
+    let event = EpollEvent { events: EPOLLIN, data: 7 };
+    epoll_ctl(epfd, EpollOp::EpollCtlAdd, timer.as_raw_fd(), &event).unwrap();
+
+    let interval = TimeSpec::nanoseconds(5_000_000);
+    timer.set(Expiration::IntervalDelayed(TimeSpec::nanoseconds(1_000_000), interval),
+              TimerSetTimeFlags::empty()).unwrap();
+
+    let mut events = [EpollEvent { events: EpollEventKind::empty(), data: 0 }];
+    for _ in 0..3 {
+        assert_eq!(epoll_wait(epfd, &mut events, 1000).unwrap(), 1);
+        assert!(timer.wait().unwrap() >= 1);
+    }
+
+    match timerfd_gettime(timer.as_raw_fd()).unwrap() {
+        Some(Expiration::IntervalDelayed(_, i)) | Some(Expiration::Interval(i)) => assert_eq!(i, interval),
+        other => panic!("unexpected timer setting {:?}", other),
+    }
+
+    close(epfd).unwrap();
+}
+
+#[test]
+fn test_timerfd_abstime() {
+    let fd = timerfd_create(ClockId::Monotonic, TimerFlags::empty()).unwrap();
+
+    // An absolute time in the past expires immediately
+    let old = timerfd_settime(fd, TFD_TIMER_ABSTIME, Some(Expiration::OneShot(TimeSpec::seconds(1)))).unwrap();
+    assert_eq!(old, None);
+
+    let timer = unsafe { TimerFd::from_raw_fd(fd) };
+    assert_eq!(timer.wait().unwrap(), 1);
+}
@@ -2,7 +2,7 @@ use nix::unistd::*;
 use nix::unistd::Fork::*;
 use nix::sys::signal::*;
 use nix::sys::wait::*;
-use libc::exit;
+use libc::{self, exit};
 
 #[test]
 fn test_wait_signal() {
@@ -28,3 +28,97 @@ fn test_wait_exit() {
       Err(_) => panic!("Error: Fork Failed")
     }
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_waitid() {
+    match fork() {
+      Ok(Child) => unsafe { exit(7); },
+      Ok(Parent(child_pid)) => {
+          let info = waitid(Id::Pid(child_pid), WEXITED).unwrap().unwrap();
+          assert_eq!(info.signal(), Ok(SIGCHLD));
+          match info.cause() {
+              SigCause::Child { uid, status } => {
+                  assert_eq!(uid, getuid());
+                  assert_eq!(status, WaitStatus::Exited(child_pid, 7));
+              }
+              cause => panic!("unexpected cause {:?}", cause),
+          }
+
+          // Already reaped
+          assert!(waitid(Id::Pid(child_pid), WEXITED | WNOHANG).is_err());
+      },
+      // panic, fork should never fail unless there is a serious problem with the OS
+      Err(_) => panic!("Error: Fork Failed")
+    }
+}
+
+#[cfg(all(target_os = "linux",
+          any(target_arch = "x86",
+              target_arch = "x86_64",
+              target_arch = "arm")),
+          )]
+#[test]
+fn test_wait_ptrace_syscall() {
+    use nix::sys::ptrace::{ptrace, ptrace_setoptions};
+    use nix::sys::ptrace::ptrace::*;
+    use std::ptr;
+
+    match fork() {
+      Ok(Child) => {
+          ptrace(PTRACE_TRACEME, 0, ptr::null_mut(), ptr::null_mut()).unwrap();
+          raise(SIGSTOP).unwrap();
+          unsafe { exit(0); }
+      },
+      Ok(Parent(child_pid)) => {
+          assert_eq!(waitpid(child_pid, None), Ok(WaitStatus::Stopped(child_pid, SIGSTOP)));
+
+          // The next stop is at a system call, which TRACESYSGOOD marks as such
+          ptrace_setoptions(child_pid, PTRACE_O_TRACESYSGOOD | PTRACE_O_TRACEEXIT).unwrap();
+          ptrace(PTRACE_SYSCALL, child_pid, ptr::null_mut(), ptr::null_mut()).unwrap();
+          assert_eq!(waitpid(child_pid, None), Ok(WaitStatus::PtraceSyscall(child_pid)));
+
+          // Running on to exit_group stops at the exit event
+          ptrace(PTRACE_CONT, child_pid, ptr::null_mut(), ptr::null_mut()).unwrap();
+          assert_eq!(waitpid(child_pid, None),
+                     Ok(WaitStatus::PtraceEvent(child_pid, SIGTRAP, PTRACE_EVENT_EXIT)));
+
+          ptrace(PTRACE_CONT, child_pid, ptr::null_mut(), ptr::null_mut()).unwrap();
+          assert_eq!(waitpid(child_pid, None), Ok(WaitStatus::Exited(child_pid, 0)));
+      },
+      // panic, fork should never fail unless there is a serious problem with the OS
+      Err(_) => panic!("Error: Fork Failed")
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_wait_unknown_signal() {
+    use std::ptr;
+
+    // Kept by the C library for itself, so there is no Signal for it
+    let signum = Signal::realtime(0).unwrap().as_raw() - 1;
+
+    match fork() {
+      Ok(Child) => unsafe {
+          // The C library handles it and refuses to reset that, so restore
+          // the default action, which kills, through the kernel directly
+          let default = [0u64; 4];
+          libc::syscall(libc::SYS_rt_sigaction, signum, &default, ptr::null_mut::<u64>(), 8);
+          libc::kill(libc::getpid(), signum);
+          exit(1);
+      },
+      Ok(Parent(child_pid)) => {
+          let info = waitid(Id::Pid(child_pid), WEXITED | WNOWAIT).unwrap().unwrap();
+          match info.cause() {
+              SigCause::Child { status, .. } => assert_eq!(status, WaitStatus::Unknown(child_pid, signum)),
+              cause => panic!("unexpected cause {:?}", cause),
+          }
+
+          // The child is reaped all the same, and the raw status kept
+          assert_eq!(waitpid(child_pid, None), Ok(WaitStatus::Unknown(child_pid, signum)));
+      },
+      // panic, fork should never fail unless there is a serious problem with the OS
+      Err(_) => panic!("Error: Fork Failed")
+    }
+}
@@ -0,0 +1,53 @@
+use nix::sys::xattr::*;
+use nix::errno::{EEXIST, ENODATA};
+use nix::Error::Sys;
+use std::ffi::OsString;
+use std::fs::File;
+use std::iter;
+use std::os::unix::io::AsRawFd;
+use tempdir::TempDir;
+
+fn sorted(names: XattrNames) -> Vec<OsString> {
+    let mut names: Vec<_> = names.filter(|name| name.to_str().unwrap().starts_with("user.")).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_xattr() {
+    let tempdir = TempDir::new("nix-test_xattr").unwrap();
+    let path = tempdir.path().join("file");
+    let file = File::create(&path).unwrap();
+    let fd = file.as_raw_fd();
+
+    match setxattr(&path, "user.checksum".as_bytes(), b"abc", XattrFlags::empty()) {
+        Err(Sys(errno)) if errno as i32 == ::libc::EOPNOTSUPP => {
+            println!("skipping test_xattr: {:?} does not support user extended attributes", tempdir.path());
+            return;
+        }
+        res => res.unwrap(),
+    }
+    assert_eq!(getxattr(&path, "user.checksum".as_bytes()).unwrap(), b"abc");
+    assert_eq!(lgetxattr(&path, "user.checksum".as_bytes()).unwrap(), b"abc");
+
+    assert_eq!(setxattr(&path, "user.checksum".as_bytes(), b"def", XATTR_CREATE).unwrap_err(), Sys(EEXIST));
+    assert_eq!(fsetxattr(fd, "user.label".as_bytes(), b"", XATTR_REPLACE).unwrap_err(), Sys(ENODATA));
+
+    // Values larger than any initial guess come back in full
+    let big: Vec<u8> = iter::repeat(0xa5).take(3000).collect();
+    fsetxattr(fd, "user.label".as_bytes(), &big, XATTR_CREATE).unwrap();
+    assert_eq!(fgetxattr(fd, "user.label".as_bytes()).unwrap(), big);
+    lsetxattr(&path, "user.empty".as_bytes(), b"", XattrFlags::empty()).unwrap();
+    assert_eq!(getxattr(&path, "user.empty".as_bytes()).unwrap(), b"");
+
+    let expected = vec![OsString::from("user.checksum"), OsString::from("user.empty"), OsString::from("user.label")];
+    assert_eq!(sorted(listxattr(&path).unwrap()), expected);
+    assert_eq!(sorted(llistxattr(&path).unwrap()), expected);
+    assert_eq!(sorted(flistxattr(fd).unwrap()), expected);
+
+    removexattr(&path, "user.checksum".as_bytes()).unwrap();
+    lremovexattr(&path, "user.empty".as_bytes()).unwrap();
+    fremovexattr(fd, "user.label".as_bytes()).unwrap();
+    assert_eq!(getxattr(&path, "user.label".as_bytes()).unwrap_err(), Sys(ENODATA));
+    assert_eq!(sorted(flistxattr(fd).unwrap()), Vec::<OsString>::new());
+}
@@ -7,6 +7,9 @@ extern crate tempdir;
 extern crate nix_test as nixtest;
 
 mod sys;
+mod test_dir;
+mod test_fcntl;
+mod test_fd;
 mod test_nix_path;
 mod test_stat;
 mod test_unistd;
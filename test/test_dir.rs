@@ -0,0 +1,61 @@
+use nix::dir::Dir;
+use nix::fcntl::{open_owned, O_RDONLY, O_DIRECTORY};
+use nix::sys::stat::{FileType, Mode};
+use std::fs::{self, File};
+use std::os::unix::io::AsRawFd;
+use tempdir::TempDir;
+
+fn sorted_names(dir: &mut Dir) -> Vec<String> {
+    let mut names: Vec<String> = dir.iter()
+        .map(|e| e.unwrap().file_name_os().to_str().unwrap().to_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_dir_iter() {
+    let tempdir = TempDir::new("nix-test_dir_iter").unwrap();
+    File::create(tempdir.path().join("file")).unwrap();
+    fs::create_dir(tempdir.path().join("subdir")).unwrap();
+
+    let mut dir = Dir::open(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    assert_eq!(sorted_names(&mut dir), vec![".", "..", "file", "subdir"]);
+
+    // The stream is exhausted until it is rewound
+    assert_eq!(dir.iter().count(), 0);
+    dir.rewind();
+
+    for entry in &mut dir {
+        let entry = entry.unwrap();
+        assert!(entry.ino() > 0);
+        let expected = match entry.file_name().to_bytes() {
+            b"file" => FileType::File,
+            _ => FileType::Directory,
+        };
+        // Some filesystems don't report d_type
+        if let Some(ty) = entry.file_type() {
+            assert_eq!(ty, expected);
+        }
+    }
+}
+
+#[test]
+fn test_dir_from_fd_seek() {
+    let tempdir = TempDir::new("nix-test_dir_from_fd_seek").unwrap();
+    File::create(tempdir.path().join("file")).unwrap();
+
+    let fd = open_owned(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    let raw = fd.as_raw_fd();
+    let mut dir = Dir::from_fd(fd).unwrap();
+    assert_eq!(dir.as_raw_fd(), raw);
+
+    let first = dir.iter().next().unwrap().unwrap();
+    let loc = dir.tell().unwrap();
+    let rest = sorted_names(&mut dir);
+    assert_eq!(rest.len(), 2);
+
+    dir.seek(loc);
+    assert_eq!(sorted_names(&mut dir), rest);
+    assert!(!rest.iter().any(|n| n.as_bytes() == first.file_name().to_bytes()));
+}
@@ -0,0 +1,423 @@
+use nix::fcntl::{openat, open, renameat, readlinkat, O_RDONLY, O_DIRECTORY, O_CREAT, O_WRONLY};
+#[cfg(target_os = "linux")]
+use nix::fcntl::{renameat2, RENAME_NOREPLACE, RENAME_EXCHANGE};
+use nix::sys::stat::Mode;
+use nix::unistd::{close, read, write};
+use std::fs::File;
+use std::io::Write;
+use std::os::unix::fs::symlink;
+use tempdir::TempDir;
+
+#[test]
+fn test_openat() {
+    const CONTENTS: &'static [u8] = b"abcd";
+    let tempdir = TempDir::new("nix-test_openat").unwrap();
+    File::create(tempdir.path().join("file")).unwrap().write_all(CONTENTS).unwrap();
+
+    let dirfd = open(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    let fd = openat(Some(dirfd), "file".as_bytes(), O_RDONLY, Mode::empty()).unwrap();
+
+    let mut buf = [0u8; 1024];
+    assert_eq!(CONTENTS.len(), read(fd, &mut buf).unwrap());
+    assert_eq!(CONTENTS, &buf[0..CONTENTS.len()]);
+
+    close(fd).unwrap();
+    close(dirfd).unwrap();
+}
+
+#[test]
+fn test_renameat() {
+    let old_dir = TempDir::new("nix-test_renameat_old").unwrap();
+    let new_dir = TempDir::new("nix-test_renameat_new").unwrap();
+    File::create(old_dir.path().join("old")).unwrap();
+
+    let old_dirfd = open(old_dir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    let new_dirfd = open(new_dir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    renameat(Some(old_dirfd), "old".as_bytes(), Some(new_dirfd), "new".as_bytes()).unwrap();
+
+    assert!(!old_dir.path().join("old").exists());
+    assert!(new_dir.path().join("new").exists());
+
+    close(old_dirfd).unwrap();
+    close(new_dirfd).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_renameat2() {
+    let tempdir = TempDir::new("nix-test_renameat2").unwrap();
+    let dirfd = open(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+
+    for &(name, contents) in &[("a", b"a"), ("b", b"b")] {
+        let fd = openat(Some(dirfd), name.as_bytes(), O_CREAT | O_WRONLY, Mode::from_bits_truncate(0o600)).unwrap();
+        write(fd, contents).unwrap();
+        close(fd).unwrap();
+    }
+
+    match renameat2(Some(dirfd), "a".as_bytes(), Some(dirfd), "b".as_bytes(), RENAME_NOREPLACE) {
+        // Not every filesystem (or kernel) supports renameat2
+        Err(nix::Error::Sys(nix::errno::EINVAL)) | Err(nix::Error::Sys(nix::errno::ENOSYS)) => return,
+        res => assert_eq!(res, Err(nix::Error::Sys(nix::errno::EEXIST))),
+    }
+
+    renameat2(Some(dirfd), "a".as_bytes(), Some(dirfd), "b".as_bytes(), RENAME_EXCHANGE).unwrap();
+
+    let fd = openat(Some(dirfd), "a".as_bytes(), O_RDONLY, Mode::empty()).unwrap();
+    let mut buf = [0u8; 1];
+    read(fd, &mut buf).unwrap();
+    assert_eq!(&buf, b"b");
+
+    close(fd).unwrap();
+    close(dirfd).unwrap();
+}
+
+#[test]
+fn test_readlinkat() {
+    let tempdir = TempDir::new("nix-test_readlinkat").unwrap();
+    // Long enough to need more than one attempt
+    let target: String = ::std::iter::repeat("x/").take(300).collect();
+    symlink(&target, tempdir.path().join("link")).unwrap();
+
+    let dirfd = open(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    assert_eq!(readlinkat(Some(dirfd), "link".as_bytes()).unwrap(), target.as_ref() as &::std::ffi::OsStr);
+    assert_eq!(readlinkat(None, &tempdir.path().join("link")).unwrap(), target.as_ref() as &::std::ffi::OsStr);
+
+    close(dirfd).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_openat2() {
+    use nix::fcntl::{openat2, OpenHow, ResolveFlag, RESOLVE_BENEATH, RESOLVE_IN_ROOT};
+    use nix::errno::{ENOSYS, EXDEV};
+    use nix::Error;
+
+    let tempdir = TempDir::new("nix-test_openat2").unwrap();
+    File::create(tempdir.path().join("file")).unwrap();
+    let dirfd = open(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+
+    let how = OpenHow::new(O_RDONLY, Mode::empty(), ResolveFlag::empty());
+    let fd = match openat2(Some(dirfd), "file".as_bytes(), &how) {
+        Err(Error::Sys(ENOSYS)) => return,
+        res => res.unwrap(),
+    };
+    close(fd).unwrap();
+
+    let how = OpenHow::new(O_RDONLY | O_DIRECTORY, Mode::empty(), RESOLVE_BENEATH);
+    assert_eq!(how.resolve(), RESOLVE_BENEATH);
+    assert_eq!(openat2(Some(dirfd), "..".as_bytes(), &how), Err(Error::Sys(EXDEV)));
+
+    // In the root, ".." of the root is the root itself
+    let how = OpenHow::new(O_RDONLY, Mode::empty(), RESOLVE_IN_ROOT);
+    let fd = openat2(Some(dirfd), "../../file".as_bytes(), &how).unwrap();
+    close(fd).unwrap();
+
+    close(dirfd).unwrap();
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod linux_zero_copy {
+    use nix::fcntl::{fcntl, splice, tee, vmsplice, FcntlRet, SpliceFFlags, loff_t};
+    use nix::fcntl::FcntlArg::{F_GETPIPE_SZ, F_SETPIPE_SZ};
+    use nix::sys::uio::IoVec;
+    use nix::unistd::{close, pipe, read, write};
+    use std::io::Write;
+    use std::os::unix::io::AsRawFd;
+    use tempdir::TempDir;
+    use std::fs::File;
+
+    #[test]
+    fn test_splice() {
+        const CONTENTS: &'static [u8] = b"abcdef123456";
+        let tempdir = TempDir::new("nix-test_splice").unwrap();
+        let mut tmp = File::create(tempdir.path().join("file")).unwrap();
+        tmp.write_all(CONTENTS).unwrap();
+        let tmp = File::open(tempdir.path().join("file")).unwrap();
+
+        let (rd, wr) = pipe().unwrap();
+        let mut offset: loff_t = 5;
+        let res = splice(tmp.as_raw_fd(), Some(&mut offset), wr, None, 2, SpliceFFlags::empty()).unwrap();
+        assert_eq!(2, res);
+        assert_eq!(7, offset);
+
+        let mut buf = [0u8; 1024];
+        assert_eq!(2, read(rd, &mut buf).unwrap());
+        assert_eq!(b"f1", &buf[0..2]);
+
+        close(rd).unwrap();
+        close(wr).unwrap();
+    }
+
+    #[test]
+    fn test_tee() {
+        let (rd1, wr1) = pipe().unwrap();
+        let (rd2, wr2) = pipe().unwrap();
+
+        write(wr1, b"abc").unwrap();
+        assert_eq!(2, tee(rd1, wr2, 2, SpliceFFlags::empty()).unwrap());
+
+        let mut buf = [0u8; 1024];
+        // Check the tee'd bytes are at rd2, and still at rd1
+        assert_eq!(2, read(rd2, &mut buf).unwrap());
+        assert_eq!(b"ab", &buf[0..2]);
+        assert_eq!(3, read(rd1, &mut buf).unwrap());
+        assert_eq!(b"abc", &buf[0..3]);
+
+        for fd in &[rd1, wr1, rd2, wr2] {
+            close(*fd).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_vmsplice() {
+        let (rd, wr) = pipe().unwrap();
+
+        let buf1 = b"abcdef";
+        let buf2 = b"defghi";
+        let iovecs = [IoVec::from_slice(&buf1[0..3]), IoVec::from_slice(&buf2[0..3])];
+        assert_eq!(6, vmsplice(wr, &iovecs, SpliceFFlags::empty()).unwrap());
+
+        let mut buf = [0u8; 32];
+        assert_eq!(6, read(rd, &mut buf).unwrap());
+        assert_eq!(b"abcdef", &buf[0..6]);
+
+        close(rd).unwrap();
+        close(wr).unwrap();
+    }
+
+    #[test]
+    fn test_pipe_size() {
+        let (rd, wr) = pipe().unwrap();
+
+        let size = match fcntl(wr, F_SETPIPE_SZ(2 * 65536)).unwrap() {
+            FcntlRet::PipeSize(size) => size,
+            ret => panic!("unexpected result {:?}", ret),
+        };
+        assert!(size >= 2 * 65536);
+        assert_eq!(fcntl(rd, F_GETPIPE_SZ), Ok(FcntlRet::PipeSize(size)));
+
+        close(rd).unwrap();
+        close(wr).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_copy_file_range() {
+        use nix::fcntl::copy_file_range;
+        use nix::errno::ENOSYS;
+        use nix::Error::Sys;
+        use std::io::Read;
+
+        const CONTENTS: &'static [u8] = b"foobarbaz";
+        let tempdir = TempDir::new("nix-test_copy_file_range").unwrap();
+        File::create(tempdir.path().join("src")).unwrap().write_all(CONTENTS).unwrap();
+        let src = File::open(tempdir.path().join("src")).unwrap();
+        let dst = File::create(tempdir.path().join("dst")).unwrap();
+
+        let mut from_offset: loff_t = 3;
+        let res = copy_file_range(src.as_raw_fd(), Some(&mut from_offset), dst.as_raw_fd(), None, 3);
+        if let Err(Sys(ENOSYS)) = res {
+            return;
+        }
+        assert_eq!(res.unwrap(), 3);
+        assert_eq!(from_offset, 6);
+
+        let mut contents = Vec::new();
+        File::open(tempdir.path().join("dst")).unwrap().read_to_end(&mut contents).unwrap();
+        assert_eq!(&contents, b"bar");
+    }
+}
+
+#[test]
+fn test_fcntl_typed_flags() {
+    use nix::fcntl::{fcntl, FcntlRet, FdFlag, FD_CLOEXEC, O_NONBLOCK};
+    use nix::fcntl::FcntlArg::{F_GETFD, F_SETFD, F_GETFL, F_SETFL, F_DUPFD_CLOEXEC};
+    use nix::unistd::pipe;
+
+    let (rd, wr) = pipe().unwrap();
+
+    assert_eq!(fcntl(rd, F_GETFD), Ok(FcntlRet::FdFlag(FdFlag::empty())));
+    assert_eq!(fcntl(rd, F_SETFD(FD_CLOEXEC)), Ok(FcntlRet::Done));
+    assert_eq!(fcntl(rd, F_GETFD), Ok(FcntlRet::FdFlag(FD_CLOEXEC)));
+
+    fcntl(wr, F_SETFL(O_NONBLOCK)).unwrap();
+    match fcntl(wr, F_GETFL).unwrap() {
+        FcntlRet::OFlag(flags) => assert!(flags.contains(O_NONBLOCK | O_WRONLY)),
+        ret => panic!("unexpected result {:?}", ret),
+    }
+
+    match fcntl(rd, F_DUPFD_CLOEXEC(100)).unwrap() {
+        FcntlRet::Fd(fd) => {
+            assert!(fd >= 100);
+            close(fd).unwrap();
+        }
+        ret => panic!("unexpected result {:?}", ret),
+    }
+
+    close(rd).unwrap();
+    close(wr).unwrap();
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_fcntl_owner_and_signal() {
+    use nix::fcntl::{fcntl, FcntlRet, FOwner, LeaseType};
+    use nix::fcntl::FcntlArg::{F_SETOWN, F_GETOWN, F_SETOWN_EX, F_GETOWN_EX, F_SETSIG, F_GETSIG, F_GETLEASE};
+    use nix::sys::signal::SIGUSR1;
+    use nix::unistd::{getpid, pipe};
+
+    let (rd, wr) = pipe().unwrap();
+    let pid = getpid();
+
+    fcntl(rd, F_SETOWN(pid)).unwrap();
+    assert_eq!(fcntl(rd, F_GETOWN), Ok(FcntlRet::Owner(pid)));
+
+    fcntl(rd, F_SETOWN_EX(FOwner::Thread(pid))).unwrap();
+    assert_eq!(fcntl(rd, F_GETOWN_EX), Ok(FcntlRet::OwnerEx(FOwner::Thread(pid))));
+
+    assert_eq!(fcntl(rd, F_GETSIG), Ok(FcntlRet::Signal(None)));
+    fcntl(rd, F_SETSIG(Some(SIGUSR1))).unwrap();
+    assert_eq!(fcntl(rd, F_GETSIG), Ok(FcntlRet::Signal(Some(SIGUSR1))));
+
+    close(rd).unwrap();
+    close(wr).unwrap();
+
+    let tempdir = TempDir::new("nix-test_fcntl_owner_and_signal").unwrap();
+    let fd = open(&tempdir.path().join("file"), O_CREAT | O_WRONLY, Mode::from_bits_truncate(0o600)).unwrap();
+    assert_eq!(fcntl(fd, F_GETLEASE), Ok(FcntlRet::Lease(LeaseType::Unlock)));
+    close(fd).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_fcntl_seals() {
+    use nix::errno::ENOSYS;
+    use nix::Error::Sys;
+    use nix::fcntl::{fcntl, FcntlRet, F_SEAL_GROW, F_SEAL_SHRINK};
+    use nix::fcntl::FcntlArg::{F_ADD_SEALS, F_GET_SEALS};
+    use nix::sys::memfd::{memfd_create, MFD_ALLOW_SEALING};
+    use std::ffi::CString;
+
+    let name = CString::new("nix-test_fcntl_seals").unwrap();
+    let fd = match memfd_create(&name, MFD_ALLOW_SEALING) {
+        Err(Sys(ENOSYS)) => return,
+        res => res.unwrap(),
+    };
+
+    fcntl(fd, F_ADD_SEALS(F_SEAL_GROW | F_SEAL_SHRINK)).unwrap();
+    assert_eq!(fcntl(fd, F_GET_SEALS), Ok(FcntlRet::SealFlag(F_SEAL_GROW | F_SEAL_SHRINK)));
+
+    close(fd).unwrap();
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_fcntl_ofd_locks() {
+    use nix::fcntl::{fcntl, flock, FcntlRet, O_RDWR};
+    use nix::fcntl::FcntlArg::{F_OFD_SETLK, F_OFD_GETLK};
+    use libc::{F_WRLCK, F_UNLCK, SEEK_SET};
+
+    let tempdir = TempDir::new("nix-test_fcntl_ofd_locks").unwrap();
+    let path = tempdir.path().join("file");
+    let fd1 = open(&path, O_CREAT | O_RDWR, Mode::from_bits_truncate(0o600)).unwrap();
+    let fd2 = open(&path, O_RDWR, Mode::empty()).unwrap();
+
+    let mut lock = flock::default();
+    lock.l_type = F_WRLCK as i16;
+    lock.l_whence = SEEK_SET as i16;
+    lock.l_len = 10;
+    assert_eq!(fcntl(fd1, F_OFD_SETLK(&lock)), Ok(FcntlRet::Done));
+
+    // The lock belongs to the open file description of fd1, so it conflicts with fd2
+    let mut query = flock::default();
+    query.l_type = F_WRLCK as i16;
+    query.l_whence = SEEK_SET as i16;
+    fcntl(fd2, F_OFD_GETLK(&mut query)).unwrap();
+    assert_eq!(query.l_type, F_WRLCK as i16);
+    assert_eq!(query.l_pid, -1);
+
+    lock.l_type = F_UNLCK as i16;
+    fcntl(fd1, F_OFD_SETLK(&lock)).unwrap();
+    // OFD queries must have l_pid zeroed
+    let mut query = flock::default();
+    query.l_type = F_WRLCK as i16;
+    query.l_whence = SEEK_SET as i16;
+    fcntl(fd2, F_OFD_GETLK(&mut query)).unwrap();
+    assert_eq!(query.l_type, F_UNLCK as i16);
+
+    close(fd1).unwrap();
+    close(fd2).unwrap();
+}
+
+#[test]
+fn test_flock_guard() {
+    use nix::fcntl::{FlockGuard, LockKind};
+    use nix::errno::EWOULDBLOCK;
+    use nix::Error::Sys;
+
+    let tempdir = TempDir::new("nix-test_flock_guard").unwrap();
+    let file1 = File::create(tempdir.path().join("file")).unwrap();
+    let file2 = File::open(tempdir.path().join("file")).unwrap();
+
+    {
+        let _guard = FlockGuard::lock(&file1, LockKind::Exclusive).unwrap();
+        assert_eq!(FlockGuard::try_lock(&file2, LockKind::Shared).unwrap_err(), Sys(EWOULDBLOCK));
+    }
+
+    let shared1 = FlockGuard::try_lock(&file1, LockKind::Shared).unwrap();
+    let shared2 = FlockGuard::try_lock(&file2, LockKind::Shared).unwrap();
+    shared1.unlock().unwrap();
+    drop(shared2);
+
+    FlockGuard::try_lock(&file2, LockKind::Exclusive).unwrap();
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_ofd_lock_guard() {
+    use nix::fcntl::{OfdLockGuard, LockConflict, LockKind, LockRange, ofd_getlk};
+    use nix::unistd::Whence;
+    use nix::errno::EAGAIN;
+    use nix::Error::Sys;
+
+    let tempdir = TempDir::new("nix-test_ofd_lock_guard").unwrap();
+    let file1 = File::create(tempdir.path().join("file")).unwrap();
+    let file2 = File::open(tempdir.path().join("file")).unwrap();
+
+    let range = LockRange::new(10, 10, Whence::SeekSet);
+    let guard = OfdLockGuard::lock(&file1, LockKind::Exclusive, range).unwrap();
+    assert_eq!(guard.range(), range);
+
+    // Overlapping ranges conflict, disjoint ones don't
+    let overlapping = LockRange::new(15, 0, Whence::SeekSet);
+    assert_eq!(OfdLockGuard::try_lock(&file2, LockKind::Shared, overlapping).unwrap_err(), Sys(EAGAIN));
+    OfdLockGuard::try_lock(&file2, LockKind::Shared, LockRange::new(0, 10, Whence::SeekSet)).unwrap();
+
+    assert_eq!(ofd_getlk(&file2, LockKind::Shared, overlapping).unwrap(),
+               Some(LockConflict { kind: LockKind::Exclusive, range: range, pid: None }));
+
+    drop(guard);
+    assert_eq!(ofd_getlk(&file2, LockKind::Exclusive, LockRange::whole_file()).unwrap(), None);
+    OfdLockGuard::try_lock(&file2, LockKind::Shared, overlapping).unwrap().unlock().unwrap();
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_ofd_lock_guard_relative_range() {
+    use nix::fcntl::{OfdLockGuard, LockKind, LockRange, ofd_getlk};
+    use nix::unistd::{lseek, Whence};
+    use std::os::unix::io::AsRawFd;
+
+    let tempdir = TempDir::new("nix-test_ofd_lock_guard_relative_range").unwrap();
+    let file1 = File::create(tempdir.path().join("file")).unwrap();
+    let file2 = File::open(tempdir.path().join("file")).unwrap();
+
+    lseek(file1.as_raw_fd(), 100, Whence::SeekSet).unwrap();
+    let guard = OfdLockGuard::lock(&file1, LockKind::Exclusive, LockRange::new(5, 10, Whence::SeekCur)).unwrap();
+    assert_eq!(guard.range(), LockRange::new(105, 10, Whence::SeekSet));
+
+    // Moving the offset must not change which bytes are unlocked
+    lseek(file1.as_raw_fd(), 0, Whence::SeekSet).unwrap();
+    drop(guard);
+    assert_eq!(ofd_getlk(&file2, LockKind::Exclusive, LockRange::whole_file()).unwrap(), None);
+}
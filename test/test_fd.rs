@@ -0,0 +1,67 @@
+use nix::fd::Fd;
+use nix::fcntl::{fcntl, open_owned, O_CLOEXEC, O_RDWR, O_CREAT};
+use nix::fcntl::FcntlArg::F_GETFD;
+use nix::sys::stat::S_IRWXU;
+use nix::unistd::{pipe, pipe2_owned, read, write, close};
+use nix::errno::Errno::EBADF;
+use nix::Error::Sys;
+use std::os::unix::io::{AsRawFd, IntoRawFd, FromRawFd};
+use tempdir::TempDir;
+
+#[test]
+fn test_fd_closes_on_drop() {
+    let (reader, writer) = pipe().unwrap();
+    let owned = unsafe { Fd::from_raw_fd(reader) };
+    assert_eq!(owned.as_raw_fd(), reader);
+
+    drop(owned);
+    assert_eq!(fcntl(reader, F_GETFD), Err(Sys(EBADF)));
+    close(writer).unwrap();
+}
+
+#[test]
+fn test_fd_into_raw_fd() {
+    let (reader, writer) = pipe2_owned(O_CLOEXEC).unwrap();
+    let raw = writer.into_raw_fd();
+
+    assert!(fcntl(raw, F_GETFD).is_ok());
+    assert_eq!(write(raw, b"x"), Ok(1));
+    close(raw).unwrap();
+
+    let mut buf = [0u8; 1];
+    assert_eq!(read(reader.as_raw_fd(), &mut buf), Ok(1));
+    assert_eq!(&buf, b"x");
+}
+
+#[test]
+fn test_fd_borrow_and_clone() {
+    let tempdir = TempDir::new("nix-test_fd_borrow_and_clone").unwrap();
+    let fd = open_owned(&tempdir.path().join("file"), O_CREAT | O_RDWR, S_IRWXU).unwrap();
+
+    let cloned = fd.borrow().to_owned().unwrap();
+    assert!(cloned.as_raw_fd() != fd.as_raw_fd());
+
+    let raw = fd.as_raw_fd();
+    fd.close().unwrap();
+    assert_eq!(fcntl(raw, F_GETFD), Err(Sys(EBADF)));
+    assert!(fcntl(cloned.as_raw_fd(), F_GETFD).is_ok());
+}
+
+#[test]
+fn test_socketpair_and_dup_owned() {
+    use nix::sys::socket::{socketpair_owned, AddressFamily, SockType, SockFlag};
+    use nix::unistd::dup_owned;
+
+    let (fd1, fd2) = socketpair_owned(AddressFamily::Unix, SockType::Stream, 0, SockFlag::empty()).unwrap();
+    let dup = dup_owned(fd1.as_raw_fd()).unwrap();
+    let raw = dup.as_raw_fd();
+
+    assert_eq!(write(raw, b"x"), Ok(1));
+    drop(dup);
+    assert_eq!(fcntl(raw, F_GETFD), Err(Sys(EBADF)));
+
+    let mut buf = [0u8; 1];
+    assert_eq!(read(fd2.as_raw_fd(), &mut buf), Ok(1));
+    assert_eq!(&buf, b"x");
+    drop(fd1);
+}
@@ -22,7 +22,7 @@ fn main() {
     assert!(opt.is_some());
 
     let info = opt.unwrap();
-    assert_eq!(info.ssi_signo as i32, signal::SIGUSR1);
+    assert_eq!(info.signal().unwrap(), signal::SIGUSR1);
 }
 
 #[cfg(not(feature = "signalfd"))]
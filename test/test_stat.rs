@@ -95,3 +95,189 @@ fn test_stat_fstat_lstat() {
     let fstat_result = fstat(link.as_raw_fd());
     assert_stat_results(fstat_result);
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_fstatat_mkdirat() {
+    use nix::fcntl::{open, O_RDONLY, O_DIRECTORY, AT_SYMLINK_NOFOLLOW, AtFlags};
+    use nix::sys::stat::{fstatat, mkdirat, Mode, S_IRWXU};
+    use nix::unistd::close;
+    use libc::S_IFDIR;
+
+    let tempdir = TempDir::new("nix-test_fstatat_mkdirat").unwrap();
+    let dirfd = open(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    File::create(tempdir.path().join("file")).unwrap();
+    symlink("file", tempdir.path().join("link")).unwrap();
+
+    mkdirat(Some(dirfd), "subdir".as_bytes(), S_IRWXU).unwrap();
+    let stats = fstatat(Some(dirfd), "subdir".as_bytes(), AtFlags::empty()).unwrap();
+    assert_eq!(stats.st_mode & S_IFMT, S_IFDIR);
+
+    assert_stat_results(fstatat(Some(dirfd), "link".as_bytes(), AtFlags::empty()));
+    assert_lstat_results(fstatat(Some(dirfd), "link".as_bytes(), AT_SYMLINK_NOFOLLOW));
+
+    close(dirfd).unwrap();
+}
+
+#[test]
+fn test_stat_wrapper() {
+    use nix::sys::stat::{Stat, FileType, S_IRUSR, S_IWUSR, S_IFDIR, S_IFMT};
+    use std::time::{Duration, SystemTime};
+
+    let tempdir = TempDir::new("nix-test_stat_wrapper").unwrap();
+    let filename = tempdir.path().join("foo.txt");
+    let file = File::create(&filename).unwrap();
+    symlink(&filename, tempdir.path().join("bar.txt")).unwrap();
+
+    let st = Stat::from(stat(&filename).unwrap());
+    assert_eq!(st.file_type(), Some(FileType::File));
+    assert_eq!(st.size(), 0);
+    assert_eq!(st.nlink(), 1);
+    assert_eq!(st.ino(), st.as_raw().st_ino as u64);
+    assert_eq!(st.mode().bits() & S_IFMT.bits(), 0);
+
+    let mtime = st.modified().unwrap();
+    let now = SystemTime::now();
+    assert!(mtime <= now && now.duration_since(mtime).unwrap() < Duration::from_secs(60));
+    assert_eq!(st.mtime().tv_sec, st.into_raw().st_mtime);
+
+    let st = Stat::from(lstat(&tempdir.path().join("bar.txt")).unwrap());
+    assert_eq!(st.file_type(), Some(FileType::Symlink));
+
+    let st = Stat::from(fstat(file.as_raw_fd()).unwrap());
+    assert!(st.mode().contains(S_IRUSR | S_IWUSR));
+
+    let st = Stat::from(stat(tempdir.path()).unwrap());
+    assert_eq!(st.file_type(), Some(FileType::Directory));
+    assert_eq!(FileType::Directory.to_sflag(), S_IFDIR);
+}
+
+#[test]
+fn test_stat_wrapper_bad_times() {
+    use nix::sys::stat::Stat;
+    use std::mem;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut raw: FileStat = unsafe { mem::zeroed() };
+    raw.st_atime = -1;
+    raw.st_atime_nsec = 500_000_000;
+    raw.st_mtime = libc::time_t::min_value();
+    raw.st_ctime_nsec = 1_000_000_000;
+
+    let st = Stat::from(raw);
+    assert_eq!(st.accessed(), Some(UNIX_EPOCH - Duration::from_millis(500)));
+    // Whether SystemTime can hold this depends on the platform, but it must
+    // not panic
+    let _ = st.modified();
+    assert_eq!(st.changed(), None);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_device_numbers() {
+    use nix::sys::stat::{Stat, FileType, mkdev, major, minor};
+
+    let st = Stat::from(stat("/dev/null".as_bytes()).unwrap());
+    assert_eq!(st.file_type(), Some(FileType::CharacterDevice));
+    assert_eq!((major(st.rdev()), minor(st.rdev())), (1, 3));
+    assert_eq!(mkdev(1, 3), st.rdev());
+
+    let dev = mkdev(0x12345, 0x6789a);
+    assert_eq!((major(dev), minor(dev)), (0x12345, 0x6789a));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_statx() {
+    use nix::Error;
+    use nix::errno::ENOSYS;
+    use nix::fcntl::{AtFlags, AT_SYMLINK_NOFOLLOW};
+    use nix::sys::stat::{statx, Stat, FileType, StatxAttr, StatxSync, STATX_BASIC_STATS, STATX_BTIME,
+                         STATX_MNT_ID, STATX_SIZE, STATX_TYPE, STATX_MODE};
+
+    let tempdir = TempDir::new("nix-test_statx").unwrap();
+    let filename = tempdir.path().join("foo.txt");
+    File::create(&filename).unwrap();
+    symlink(&filename, tempdir.path().join("bar.txt")).unwrap();
+
+    let stx = match statx(None, &filename, AtFlags::empty(), StatxSync::AsStat,
+                         STATX_BASIC_STATS | STATX_BTIME | STATX_MNT_ID) {
+        Err(Error::Sys(ENOSYS)) => return,
+        res => res.unwrap(),
+    };
+    let st = Stat::from(stat(&filename).unwrap());
+
+    assert!(stx.mask().contains(STATX_BASIC_STATS));
+    assert_eq!(stx.file_type(), Some(FileType::File));
+    assert_eq!(stx.mode(), Some(st.mode()));
+    assert_eq!(stx.ino(), Some(st.ino()));
+    assert_eq!(stx.size(), Some(0));
+    assert_eq!(stx.nlink(), Some(1));
+    assert_eq!(stx.uid(), Some(st.uid()));
+    assert_eq!(stx.dev(), st.dev());
+    assert_eq!(stx.mtime(), Some(st.mtime()));
+    assert_eq!(stx.blksize() as i64, st.blksize());
+    if let Some(btime) = stx.btime() {
+        assert!(btime <= stx.ctime().unwrap());
+    }
+
+    // Only the requested fields are guaranteed, anything else is optional
+    let stx = statx(None, &tempdir.path().join("bar.txt"), AT_SYMLINK_NOFOLLOW, StatxSync::DontSync,
+                    STATX_TYPE | STATX_SIZE).unwrap();
+    assert_eq!(stx.file_type(), Some(FileType::Symlink));
+    assert!(stx.size().is_some());
+    assert_eq!(stx.mode().is_some(), stx.mask().contains(STATX_MODE));
+    assert!(stx.attributes() & !stx.attributes_mask() == StatxAttr::empty());
+}
+
+#[test]
+fn test_mkdir_mkfifo_chmod() {
+    use nix::sys::stat::{Stat, FileType, Mode, mkdir, mkfifo, chmod, fchmod, S_IRWXU, S_IRUSR, S_IWUSR};
+
+    let tempdir = TempDir::new("nix-test_mkdir_mkfifo_chmod").unwrap();
+
+    mkdir(&tempdir.path().join("dir"), S_IRWXU).unwrap();
+    assert!(mkdir(&tempdir.path().join("dir"), S_IRWXU).is_err());
+    let st = Stat::from(stat(&tempdir.path().join("dir")).unwrap());
+    assert_eq!(st.file_type(), Some(FileType::Directory));
+    assert_eq!(st.mode(), S_IRWXU);
+
+    mkfifo(&tempdir.path().join("fifo"), S_IRUSR | S_IWUSR).unwrap();
+    let st = Stat::from(stat(&tempdir.path().join("fifo")).unwrap());
+    assert_eq!(st.file_type(), Some(FileType::Fifo));
+
+    let filename = tempdir.path().join("file");
+    let file = File::create(&filename).unwrap();
+    chmod(&filename, S_IRUSR).unwrap();
+    assert_eq!(Stat::from(stat(&filename).unwrap()).mode(), S_IRUSR);
+    fchmod(file.as_raw_fd(), Mode::empty()).unwrap();
+    assert_eq!(Stat::from(stat(&filename).unwrap()).mode(), Mode::empty());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_utimensat_futimens() {
+    use nix::fcntl::AtFlags;
+    use nix::sys::stat::{Stat, utimensat, futimens, UTIME_NOW, UTIME_OMIT};
+    use nix::sys::time::TimeSpec;
+
+    let tempdir = TempDir::new("nix-test_utimensat_futimens").unwrap();
+    let filename = tempdir.path().join("file");
+    let file = File::create(&filename).unwrap();
+
+    let atime = TimeSpec { tv_sec: 1_000_000_000, tv_nsec: 123 };
+    let mtime = TimeSpec { tv_sec: 1_500_000_000, tv_nsec: 456 };
+    utimensat(None, &filename, &atime, &mtime, AtFlags::empty()).unwrap();
+    let st = Stat::from(stat(&filename).unwrap());
+    assert_eq!((st.atime(), st.mtime()), (atime, mtime));
+
+    // Omitted times are left alone
+    utimensat(None, &filename, &UTIME_OMIT, &atime, AtFlags::empty()).unwrap();
+    let st = Stat::from(stat(&filename).unwrap());
+    assert_eq!((st.atime(), st.mtime()), (atime, atime));
+
+    futimens(file.as_raw_fd(), &UTIME_NOW, &UTIME_OMIT).unwrap();
+    let st = Stat::from(fstat(file.as_raw_fd()).unwrap());
+    assert!(st.atime() > mtime);
+    assert_eq!(st.mtime(), atime);
+}
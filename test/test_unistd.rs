@@ -106,3 +106,234 @@ execve_test_factory!(test_execve, execve, b"/bin/sh", b"/system/bin/sh");
 #[cfg(any(target_os = "linux", target_os = "android"))]
 #[cfg(feature = "execvpe")]
 execve_test_factory!(test_execvpe, execvpe, b"sh", b"sh");
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_at_functions() {
+    use nix::fcntl::{open, O_RDONLY, O_DIRECTORY, AT_REMOVEDIR, AT_SYMLINK_NOFOLLOW, AtFlags};
+    use nix::sys::stat::{Mode, mkdirat, S_IRWXU};
+    use std::fs::File;
+    use tempdir::TempDir;
+
+    let tempdir = TempDir::new("nix-test_at_functions").unwrap();
+    let dirfd = open(tempdir.path(), O_RDONLY | O_DIRECTORY, Mode::empty()).unwrap();
+    File::create(tempdir.path().join("file")).unwrap();
+
+    linkat(Some(dirfd), "file".as_bytes(), Some(dirfd), "hardlink".as_bytes(), AtFlags::empty()).unwrap();
+    symlinkat("file".as_bytes(), Some(dirfd), "symlink".as_bytes()).unwrap();
+    assert!(tempdir.path().join("hardlink").is_file());
+    assert!(tempdir.path().join("symlink").is_file());
+
+    assert!(faccessat(Some(dirfd), "file".as_bytes(), R_OK | W_OK, AtFlags::empty()).is_ok());
+    assert!(faccessat(Some(dirfd), "missing".as_bytes(), F_OK, AtFlags::empty()).is_err());
+    fchownat(Some(dirfd), "symlink".as_bytes(), None, None, AT_SYMLINK_NOFOLLOW).unwrap();
+
+    unlinkat(Some(dirfd), "symlink".as_bytes(), AtFlags::empty()).unwrap();
+    assert!(!tempdir.path().join("symlink").exists());
+
+    mkdirat(Some(dirfd), "dir".as_bytes(), S_IRWXU).unwrap();
+    assert!(unlinkat(Some(dirfd), "dir".as_bytes(), AtFlags::empty()).is_err());
+    unlinkat(Some(dirfd), "dir".as_bytes(), AT_REMOVEDIR).unwrap();
+    assert!(!tempdir.path().join("dir").exists());
+
+    close(dirfd).unwrap();
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_file_space_management() {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use tempdir::TempDir;
+
+    let tempdir = TempDir::new("nix-test_file_space_management").unwrap();
+    let mut file = OpenOptions::new().read(true).write(true).create(true)
+                                     .open(tempdir.path().join("file")).unwrap();
+    let fd = file.as_raw_fd();
+
+    fallocate(fd, FallocateFlags::empty(), 0, 8192).unwrap();
+    assert_eq!(file.metadata().unwrap().len(), 8192);
+
+    // Keeping the size only reserves the blocks
+    fallocate(fd, FALLOC_FL_KEEP_SIZE, 8192, 4096).unwrap();
+    assert_eq!(file.metadata().unwrap().len(), 8192);
+
+    file.write_all(&[0xff; 8192]).unwrap();
+    fallocate(fd, FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE, 0, 4096).unwrap();
+
+    let mut buf = Vec::new();
+    ::std::fs::File::open(tempdir.path().join("file")).unwrap().read_to_end(&mut buf).unwrap();
+    assert_eq!(buf.len(), 8192);
+    assert!(buf[..4096].iter().all(|&b| b == 0));
+    assert!(buf[4096..].iter().all(|&b| b == 0xff));
+
+    posix_fadvise(fd, 0, 0, PosixFadviseAdvice::Sequential).unwrap();
+    posix_fadvise(fd, 0, 4096, PosixFadviseAdvice::DontNeed).unwrap();
+    readahead(fd, 0, 8192).unwrap();
+
+    sync_file_range(fd, 0, 0, SYNC_FILE_RANGE_WAIT_BEFORE | SYNC_FILE_RANGE_WRITE | SYNC_FILE_RANGE_WAIT_AFTER).unwrap();
+    syncfs(fd).unwrap();
+    fsync(fd).unwrap();
+    fdatasync(fd).unwrap();
+
+    assert!(fallocate(fd, FallocateFlags::empty(), 0, 0).is_err());
+}
+
+#[test]
+fn test_lseek() {
+    use std::fs::File;
+    use std::io::Write;
+    use std::os::unix::io::AsRawFd;
+    use tempdir::TempDir;
+
+    let tempdir = TempDir::new("nix-test_lseek").unwrap();
+    let mut file = File::create(tempdir.path().join("file")).unwrap();
+    file.write_all(b"0123456789").unwrap();
+    let fd = file.as_raw_fd();
+
+    assert_eq!(lseek(fd, 0, Whence::SeekCur).unwrap(), 10);
+    assert_eq!(lseek(fd, 2, Whence::SeekSet).unwrap(), 2);
+    assert_eq!(lseek(fd, 3, Whence::SeekCur).unwrap(), 5);
+    assert_eq!(lseek(fd, -1, Whence::SeekEnd).unwrap(), 9);
+    assert!(lseek(fd, -20, Whence::SeekCur).is_err());
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    assert_eq!(lseek64(fd, 1 << 33, Whence::SeekSet).unwrap(), 1 << 33);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+#[test]
+fn test_extents() {
+    use nix::sys::uio::pwrite;
+    use std::fs::File;
+    use std::os::unix::io::AsRawFd;
+    use tempdir::TempDir;
+
+    let tempdir = TempDir::new("nix-test_extents").unwrap();
+    let file = File::create(tempdir.path().join("file")).unwrap();
+    let fd = file.as_raw_fd();
+
+    assert_eq!(Extents::new(fd).unwrap().count(), 0);
+
+    ftruncate(fd, 2 << 20).unwrap();
+    pwrite(fd, &[1; 4096], 0).unwrap();
+    pwrite(fd, &[1; 4096], 1 << 20).unwrap();
+    fsync(fd).unwrap();
+
+    assert_eq!(lseek(fd, 0, Whence::SeekData).unwrap(), 0);
+
+    let extents: Vec<_> = Extents::new(fd).unwrap().map(|e| e.unwrap()).collect();
+
+    // Whatever the filesystem reports, the extents cover the whole file in
+    // order, alternate between data and hole, and contain the written data.
+    let mut pos = 0;
+    for (i, extent) in extents.iter().enumerate() {
+        assert_eq!(extent.offset, pos);
+        assert!(extent.len > 0);
+        if i > 0 {
+            assert!(extent.kind != extents[i - 1].kind);
+        }
+        pos += extent.len;
+    }
+    assert_eq!(pos, 2 << 20);
+
+    for &offset in &[0, 1 << 20] {
+        assert!(extents.iter().any(|e| e.kind == ExtentKind::Data &&
+                                       e.offset <= offset && offset + 4096 <= e.offset + e.len));
+    }
+}
+
+#[test]
+fn test_chown() {
+    use nix::sys::stat::{lstat, stat};
+    use std::fs::File;
+    use std::os::unix::fs::symlink;
+    use std::os::unix::io::AsRawFd;
+    use tempdir::TempDir;
+
+    let tempdir = TempDir::new("nix-test_chown").unwrap();
+    let filename = tempdir.path().join("file");
+    let file = File::create(&filename).unwrap();
+    symlink(&filename, tempdir.path().join("link")).unwrap();
+
+    // Changing to the current ids is always permitted
+    let (uid, gid) = (getuid(), getgid());
+    chown(&filename, None, None).unwrap();
+    chown(&filename, Some(uid), None).unwrap();
+    fchown(file.as_raw_fd(), None, Some(gid)).unwrap();
+    lchown(&tempdir.path().join("link"), Some(uid), Some(gid)).unwrap();
+
+    let st = stat(&filename).unwrap();
+    assert_eq!((st.st_uid, st.st_gid), (uid, gid));
+    let st = lstat(&tempdir.path().join("link")).unwrap();
+    assert_eq!((st.st_uid, st.st_gid), (uid, gid));
+
+    assert!(chown(&tempdir.path().join("missing"), None, None).is_err());
+}
+
+#[test]
+fn test_link_symlink_rename() {
+    use std::fs::File;
+    use std::io::Write;
+    use std::iter;
+    use tempdir::TempDir;
+
+    let tempdir = TempDir::new("nix-test_link_symlink_rename").unwrap();
+    let path = tempdir.path();
+    File::create(path.join("file")).unwrap().write_all(b"data").unwrap();
+
+    link(&path.join("file"), &path.join("hardlink")).unwrap();
+    assert!(link(&path.join("file"), &path.join("hardlink")).is_err());
+    assert_eq!(::std::fs::metadata(path.join("file")).unwrap().len(), 4);
+
+    symlink("file".as_bytes(), &path.join("symlink")).unwrap();
+    assert_eq!(readlink(&path.join("symlink")).unwrap(), "file");
+    assert!(readlink(&path.join("file")).is_err());
+
+    // Targets longer than the initial buffer are read in full
+    let long: String = iter::repeat("x/").take(1000).collect();
+    symlink(long.as_bytes(), &path.join("long")).unwrap();
+    assert_eq!(readlink(&path.join("long")).unwrap(), &long[..]);
+
+    rename(&path.join("hardlink"), &path.join("renamed")).unwrap();
+    assert!(!path.join("hardlink").exists());
+    assert!(path.join("renamed").is_file());
+
+    let canonical = ::std::fs::canonicalize(path).unwrap();
+    assert_eq!(realpath(&path.join("symlink")).unwrap(), canonical.join("file"));
+    assert_eq!(realpath(&path.join(".").join("..").join(path.file_name().unwrap())).unwrap(), canonical);
+    assert!(realpath(&path.join("missing")).is_err());
+}
+
+#[test]
+fn test_itimer_alarm() {
+    use nix::sys::time::TimeVal;
+
+    // Far enough in the future that no signal is ever delivered
+    let setting = ITimerVal { it_interval: TimeVal::zero(), it_value: TimeVal::seconds(100) };
+    let disarm = ITimerVal { it_interval: TimeVal::zero(), it_value: TimeVal::zero() };
+
+    assert_eq!(setitimer(Which::Virtual, &setting).unwrap(), disarm);
+    // CPU-time timers are rounded up to the scheduler tick
+    assert!(getitimer(Which::Virtual).unwrap().it_value > TimeVal::seconds(99));
+    setitimer(Which::Virtual, &disarm).unwrap();
+    assert_eq!(getitimer(Which::Virtual).unwrap(), disarm);
+
+    // alarm shares the real-time interval timer
+    assert_eq!(alarm(100), None);
+    assert!(getitimer(Which::Real).unwrap().it_value > TimeVal::seconds(99));
+    assert_eq!(alarm(0), Some(100));
+    assert_eq!(getitimer(Which::Real).unwrap(), disarm);
+}
+
+#[test]
+fn test_sleep() {
+    use nix::sys::time::{ClockId, TimeSpec, clock_gettime};
+
+    assert_eq!(sleep(0), 0);
+
+    let start = clock_gettime(ClockId::Monotonic).unwrap();
+    assert_eq!(nanosleep(&TimeSpec::milliseconds(10)).unwrap(), None);
+    assert!(clock_gettime(ClockId::Monotonic).unwrap() - start >= TimeSpec::milliseconds(10));
+}